@@ -0,0 +1,92 @@
+//! Input and errors shared by the binaries.
+
+use rlp_rs::RlpError;
+use std::fmt::{self, Display};
+use std::io::{self, Read};
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum CliError {
+    Usage(String),
+    Invalid(String),
+    Io(io::Error),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Invalid(_) => 1,
+            CliError::Usage(_) => 2,
+            CliError::Io(_) => 3,
+        }
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Usage(message) | CliError::Invalid(message) => formatter.write_str(message),
+            CliError::Io(error) => write!(formatter, "I/O error: {error}"),
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(error: io::Error) -> Self {
+        CliError::Io(error)
+    }
+}
+
+impl From<RlpError> for CliError {
+    fn from(error: RlpError) -> Self {
+        CliError::Invalid(format!("invalid RLP: {error}"))
+    }
+}
+
+/// the input itself if it is hex, the content of the input file, or stdin.
+/// An input starting with `0x` is always hex, an existing file is preferred to bare hex.
+pub fn read_input(input: Option<&str>, stdin: &mut dyn Read) -> Result<Vec<u8>, CliError> {
+    let bytes = match input {
+        None | Some("-") => {
+            let mut bytes = Vec::new();
+            stdin.read_to_end(&mut bytes)?;
+            bytes
+        }
+        Some(hex) if hex.starts_with("0x") => hex.as_bytes().to_vec(),
+        Some(path) if Path::new(path).exists() => std::fs::read(path)?,
+        Some(hex) if parse_hex(hex.as_bytes()).is_some() => hex.as_bytes().to_vec(),
+        Some(path) => {
+            return Err(CliError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{path} is neither hex nor an existing file"),
+            )))
+        }
+    };
+
+    if bytes.is_empty() {
+        return Err(CliError::Invalid(String::from("empty input")));
+    }
+    Ok(bytes)
+}
+
+/// decode hex input, `None` if it is not hex.
+pub fn parse_hex(input: &[u8]) -> Option<Vec<u8>> {
+    let text = std::str::from_utf8(input).ok()?.trim();
+    hex::decode(text.strip_prefix("0x").unwrap_or(text)).ok()
+}
+
+/// the RLP bytes of hex or binary input.
+pub fn rlp_bytes(input: &[u8]) -> Result<Vec<u8>, CliError> {
+    let bytes = match parse_hex(input) {
+        Some(bytes) => bytes,
+        None if input.starts_with(b"0x") => {
+            return Err(CliError::Invalid(String::from("invalid hex")))
+        }
+        None => input.to_vec(),
+    };
+
+    if bytes.is_empty() {
+        return Err(CliError::Invalid(String::from("empty input")));
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,343 @@
+mod input;
+
+use input::{parse_hex, read_input, rlp_bytes, CliError};
+use rlp_rs::{dump, pack_rlp, visit_rlp, Rlp, RlpVisitor};
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: rlp <command> [options] [input]
+
+Commands:
+    decode      print the annotated tree of RLP bytes
+    encode      encode a JSON tree in the Ethereum tests style, print it in hex
+    validate    check that the input is a single canonical item within the limits
+    convert     convert hex to binary, or binary to hex
+
+The input is a hex string, a file, or stdin when missing or `-`.
+An argument starting with `0x` is always hex, otherwise an existing file is
+preferred to bare hex. Any other argument is an I/O error.
+The content of a file or stdin that is not hex is taken as binary.
+
+Options:
+    --max-depth <n>     validate: maximum nesting of lists
+    --max-len <n>       validate: maximum length of the encoding in bytes
+    --to <hex|bin>      convert: output format, the opposite of the input by default
+    -h, --help          print this message
+
+Exit codes:
+    0   success
+    1   invalid input (RLP, JSON or hex) or limit exceeded
+    2   invalid usage
+    3   I/O error
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Hex,
+    Bin,
+}
+
+#[derive(Debug, Default)]
+struct Args {
+    command: String,
+    input: Option<String>,
+    max_depth: Option<usize>,
+    max_len: Option<usize>,
+    to: Option<Format>,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, CliError> {
+    let mut parsed = Args::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| CliError::Usage(format!("missing value for {name}")))
+        };
+        let number = |name: &str, value: &String| {
+            value
+                .parse::<usize>()
+                .map_err(|_| CliError::Usage(format!("invalid value for {name}: {value}")))
+        };
+
+        match arg.as_str() {
+            "--max-depth" => parsed.max_depth = Some(number(arg, value(arg)?)?),
+            "--max-len" => parsed.max_len = Some(number(arg, value(arg)?)?),
+            "--to" => {
+                parsed.to = match value(arg)?.as_str() {
+                    "hex" => Some(Format::Hex),
+                    "bin" => Some(Format::Bin),
+                    other => {
+                        return Err(CliError::Usage(format!("invalid value for --to: {other}")))
+                    }
+                }
+            }
+            option if option.starts_with("--") => {
+                return Err(CliError::Usage(format!("unknown option {option}")))
+            }
+            _ if parsed.command.is_empty() => parsed.command = arg.clone(),
+            _ if parsed.input.is_none() => parsed.input = Some(arg.clone()),
+            _ => return Err(CliError::Usage(format!("unexpected argument {arg}"))),
+        }
+    }
+
+    match parsed.command.as_str() {
+        "" => Err(CliError::Usage(String::from("missing command"))),
+        "decode" | "encode" | "validate" | "convert" => Ok(parsed),
+        command => Err(CliError::Usage(format!("unknown command {command}"))),
+    }
+}
+
+/// Shape of the input, gathered while validating it.
+#[derive(Debug, Default)]
+struct Stats {
+    depth: usize,
+    max_depth: usize,
+    top_level: usize,
+    strings: usize,
+    lists: usize,
+}
+
+impl RlpVisitor for Stats {
+    fn enter_list(&mut self, _len: usize, _offset: usize) {
+        if self.depth == 0 {
+            self.top_level += 1;
+        }
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+        self.lists += 1;
+    }
+
+    fn exit_list(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn data(&mut self, _bytes: &[u8], _offset: usize) {
+        if self.depth == 0 {
+            self.top_level += 1;
+        }
+        self.strings += 1;
+    }
+}
+
+fn validate(bytes: &[u8], args: &Args) -> Result<Stats, CliError> {
+    if let Some(max_len) = args.max_len.filter(|max_len| bytes.len() > *max_len) {
+        return Err(CliError::Invalid(format!(
+            "too long: {} bytes, the maximum is {max_len}",
+            bytes.len()
+        )));
+    }
+
+    let mut stats = Stats::default();
+    visit_rlp(bytes, &mut stats)?;
+    if stats.top_level != 1 {
+        return Err(CliError::Invalid(format!(
+            "expected a single item, found {}",
+            stats.top_level
+        )));
+    }
+    if let Some(max_depth) = args
+        .max_depth
+        .filter(|max_depth| stats.max_depth > *max_depth)
+    {
+        return Err(CliError::Invalid(format!(
+            "too deep: {} nested lists, the maximum is {max_depth}",
+            stats.max_depth
+        )));
+    }
+    Ok(stats)
+}
+
+fn run(args: &[String], stdin: &mut dyn Read, stdout: &mut dyn Write) -> Result<(), CliError> {
+    let args = parse_args(args)?;
+    let input = read_input(args.input.as_deref(), stdin)?;
+
+    match args.command.as_str() {
+        "decode" => {
+            let bytes = rlp_bytes(&input)?;
+            stdout.write_all(dump(&bytes)?.as_bytes())?;
+        }
+        "encode" => {
+            let json = serde_json::from_slice(&input)
+                .map_err(|error| CliError::Invalid(format!("invalid JSON: {error}")))?;
+            let bytes = pack_rlp(Rlp::from_json(&json)?)?;
+            writeln!(stdout, "0x{}", hex::encode(bytes))?;
+        }
+        "validate" => {
+            let bytes = rlp_bytes(&input)?;
+            let stats = validate(&bytes, &args)?;
+            writeln!(
+                stdout,
+                "valid: {} bytes, {} strings, {} lists, depth {}",
+                bytes.len(),
+                stats.strings,
+                stats.lists,
+                stats.max_depth
+            )?;
+        }
+        "convert" => {
+            let hex = parse_hex(&input);
+            let to = args.to.unwrap_or(match hex {
+                Some(_) => Format::Bin,
+                None => Format::Hex,
+            });
+            match (to, hex) {
+                (Format::Bin, Some(bytes)) => stdout.write_all(&bytes)?,
+                (Format::Bin, None) => return Err(CliError::Invalid(String::from("invalid hex"))),
+                (Format::Hex, _) => writeln!(stdout, "0x{}", hex::encode(&input))?,
+            }
+        }
+        command => unreachable!("unknown command {command} after parsing"),
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match run(&args, &mut io::stdin().lock(), &mut io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("rlp: {error}");
+            if let CliError::Usage(_) = error {
+                eprint!("\n{USAGE}");
+            }
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run, CliError};
+
+    fn run_with(args: &[&str], stdin: &[u8]) -> Result<Vec<u8>, CliError> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let mut stdout = Vec::new();
+        run(&args, &mut &stdin[..], &mut stdout)?;
+        Ok(stdout)
+    }
+
+    fn exit_code(result: Result<Vec<u8>, CliError>) -> u8 {
+        result.map_or_else(|error| error.exit_code(), |_| 0)
+    }
+
+    #[test]
+    fn decode() {
+        let tree =
+            String::from_utf8(run_with(&["decode", "0xc88363617483646f67"], &[]).unwrap()).unwrap();
+        assert_eq!(
+            tree.lines().collect::<Vec<_>>(),
+            [
+                "000000  c8 short list len=8",
+                "000001    83 short string len=3 0x636174 \"cat\"",
+                "000005    83 short string len=3 0x646f67 \"dog\"",
+            ]
+        );
+
+        // binary from stdin
+        let tree = run_with(&["decode"], &[0x83, b'c', b'a', b't']).unwrap();
+        assert!(String::from_utf8(tree).unwrap().contains("\"cat\""));
+
+        assert_eq!(exit_code(run_with(&["decode", "0xc883"], &[])), 1);
+        assert_eq!(exit_code(run_with(&["decode", "0xzz"], &[])), 1);
+    }
+
+    #[test]
+    fn encode() {
+        let hex = run_with(&["encode"], br##"["cat", ["0x01", "#1024"], []]"##).unwrap();
+        assert_eq!(hex, b"0xca83636174c401820400c0\n");
+
+        assert_eq!(exit_code(run_with(&["encode"], b"[\"cat\"")), 1);
+        assert_eq!(exit_code(run_with(&["encode"], b"[null]")), 1);
+    }
+
+    #[test]
+    fn validate() {
+        let tests: [(&[&str], u8); 9] = [
+            (&["validate", "0xc88363617483646f67"], 0),
+            (&["validate", "0xc88363617483646f67", "--max-len", "9"], 0),
+            (&["validate", "0xc88363617483646f67", "--max-len", "8"], 1),
+            (&["validate", "0xc3c2c100", "--max-depth", "3"], 0),
+            (&["validate", "0xc3c2c100", "--max-depth", "2"], 1),
+            // not canonical: a single byte below 0x80 with a prefix
+            (&["validate", "0x8100"], 1),
+            // two items
+            (&["validate", "0x0102"], 1),
+            (&["validate", "0x01", "--max-depth"], 2),
+            (&["validate", "0x01", "--max-depth", "deep"], 2),
+        ];
+
+        for (i, (args, code)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            assert_eq!(exit_code(run_with(args, &[])), code);
+
+            println!("ok");
+        }
+
+        let summary = run_with(&["validate", "0xc3c2c100"], &[]).unwrap();
+        assert_eq!(summary, b"valid: 4 bytes, 1 strings, 3 lists, depth 3\n");
+    }
+
+    #[test]
+    fn convert() {
+        let binary = run_with(&["convert", "0xc101"], &[]).unwrap();
+        assert_eq!(binary, [0xc1, 0x01]);
+
+        let hex = run_with(&["convert"], &binary).unwrap();
+        assert_eq!(hex, b"0xc101\n");
+
+        // hex of the hex text
+        let hex = run_with(&["convert", "--to", "hex", "0x"], &[]).unwrap();
+        assert_eq!(hex, b"0x3078\n");
+
+        assert_eq!(exit_code(run_with(&["convert", "--to", "bin"], &[0xff])), 1);
+        assert_eq!(exit_code(run_with(&["convert", "--to", "oct"], &[])), 2);
+    }
+
+    #[test]
+    fn input() {
+        let path = std::env::temp_dir().join(format!("rlp-cli-input-{}", std::process::id()));
+        std::fs::write(&path, [0x83, b'c', b'a', b't']).unwrap();
+        let tree = run_with(&["decode", path.to_str().unwrap()], &[]).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(String::from_utf8(tree).unwrap().contains("\"cat\""));
+
+        // bare hex
+        assert!(run_with(&["validate", "c101"], &[]).is_ok());
+
+        let tests: [(&[&str], &[u8], u8); 6] = [
+            // neither hex nor an existing file
+            (&["validate", "x"], &[], 3),
+            (&["decode", "no_such_file.bin"], &[], 3),
+            (&["decode"], &[], 1),
+            (&["decode", "-"], &[], 1),
+            (&["decode", "0x"], &[], 1),
+            (&["validate"], b"0x", 1),
+        ];
+
+        for (i, (args, stdin, code)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            assert_eq!(exit_code(run_with(args, stdin)), code);
+
+            println!("ok");
+        }
+    }
+
+    #[test]
+    fn usage() {
+        assert_eq!(exit_code(run_with(&[], &[])), 2);
+        assert_eq!(exit_code(run_with(&["explode"], &[])), 2);
+        assert_eq!(exit_code(run_with(&["decode", "--verbose"], &[])), 2);
+        assert_eq!(exit_code(run_with(&["decode", "0x01", "0x02"], &[])), 2);
+    }
+}
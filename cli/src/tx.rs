@@ -0,0 +1,214 @@
+mod input;
+
+use input::{read_input, rlp_bytes, CliError};
+use rlp_rs::schema::Value;
+use rlp_types::{Field, TransactionEnvelope};
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: rlp-tx [--json] [input]
+
+Decode a signed transaction, print its fields and hash, and check that
+it encodes back to the same bytes.
+
+The input is the hex string sent to `eth_sendRawTransaction`, a file with
+that string or with the binary transaction, or stdin when missing or `-`.
+An argument that is neither hex nor an existing file is an I/O error.
+Typed transactions wrapped in an RLP string, as in a block body, are accepted too.
+
+Options:
+    --json      print a JSON object instead of text
+    -h, --help  print this message
+
+Exit codes:
+    0   success
+    1   invalid transaction, or the encoding doesn't round trip
+    2   invalid usage
+    3   I/O error
+";
+
+/// `field` as printed in text: integers in decimal, words one per line.
+fn text(field: &Field) -> String {
+    match field {
+        Field::Quantity(bytes) => Value::Uint(bytes.to_vec()).to_string(),
+        Field::Uint(value) => value.to_string(),
+        Field::Data(bytes) => format!("0x{}", hex::encode(bytes)),
+        Field::Words(words) => words
+            .iter()
+            .map(|word| format!("\n    0x{}", hex::encode(word)))
+            .collect(),
+        Field::AccessList(access_list) => access_list
+            .iter()
+            .map(|(address, keys)| {
+                let keys: String = keys
+                    .iter()
+                    .map(|key| format!("\n        0x{}", hex::encode(key)))
+                    .collect();
+                format!("\n    0x{}{keys}", hex::encode(address))
+            })
+            .collect(),
+    }
+}
+
+fn run(args: &[String], stdin: &mut dyn Read, stdout: &mut dyn Write) -> Result<(), CliError> {
+    let mut json = false;
+    let mut input = None;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            option if option.starts_with("--") => {
+                return Err(CliError::Usage(format!("unknown option {option}")))
+            }
+            _ if input.is_none() => input = Some(arg.as_str()),
+            _ => return Err(CliError::Usage(format!("unexpected argument {arg}"))),
+        }
+    }
+
+    let bytes = rlp_bytes(&read_input(input, stdin)?)?;
+    let tx = TransactionEnvelope::from_raw_bytes(&bytes)?;
+    let fields = tx.fields()?;
+    // the input is either the raw transaction or, as in a block body, its encoding
+    let round_trip = tx.to_raw_bytes()? == bytes || rlp_rs::to_bytes(&tx)? == bytes;
+
+    if json {
+        let mut output = rlp_types::json::transaction(&tx)?;
+        output["round_trip"] = round_trip.into();
+        writeln!(stdout, "{output:#}")?;
+    } else {
+        writeln!(stdout, "type: {} ({})", tx.tx_type(), tx.name())?;
+        writeln!(stdout, "hash: 0x{}", hex::encode(tx.hash()?))?;
+        for (name, field) in &fields {
+            writeln!(stdout, "{name}: {}", text(field))?;
+        }
+        let result = if round_trip { "ok" } else { "mismatch" };
+        writeln!(stdout, "round trip: {result}")?;
+    }
+
+    match round_trip {
+        true => Ok(()),
+        false => Err(CliError::Invalid(String::from(
+            "the transaction doesn't encode back to the input",
+        ))),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match run(&args, &mut io::stdin().lock(), &mut io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("rlp-tx: {error}");
+            if let CliError::Usage(_) = error {
+                eprint!("\n{USAGE}");
+            }
+            ExitCode::from(error.exit_code())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run, CliError};
+    use sha3::{Digest, Keccak256};
+
+    const LEGACY: &str = "f85f800a82c35094095e7baea6a6c7c4c2dfeb977efac326af552d870a801ba09bea4c4daac7c7c52e093e6a4c35dbbcf8856f1af7b059ba20253e70848d094fa08a8fae537ce25ed8cb5af9adac3f141af69bd515bd2ba031522df09b97dd72b1";
+    const DYNAMIC_FEE: &str = "02f8a0018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8";
+
+    fn run_with(args: &[&str], stdin: &[u8]) -> Result<String, CliError> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let mut stdout = Vec::new();
+        run(&args, &mut &stdin[..], &mut stdout)?;
+        Ok(String::from_utf8(stdout).unwrap())
+    }
+
+    fn tx_hash(tx: &str) -> String {
+        let hash = Keccak256::digest(hex::decode(tx).unwrap());
+        format!("0x{}", hex::encode(hash))
+    }
+
+    #[test]
+    fn inspect_text() {
+        let output = run_with(&[&format!("0x{LEGACY}")], &[]).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "type: 0 (legacy)");
+        assert_eq!(lines[1], format!("hash: {}", tx_hash(LEGACY)));
+        assert_eq!(
+            &lines[2..8],
+            [
+                "nonce: 0",
+                "gas_price: 10",
+                "gas_limit: 50000",
+                "to: 0x095e7baea6a6c7c4c2dfeb977efac326af552d87",
+                "value: 10",
+                "data: 0x",
+            ]
+        );
+        assert_eq!(lines[8], "v: 27");
+        assert_eq!(lines.last(), Some(&"round trip: ok"));
+
+        let output = run_with(&[], DYNAMIC_FEE.as_bytes()).unwrap();
+        assert!(output.starts_with("type: 2 (dynamic fee)\n"));
+        assert!(output.contains(&format!("hash: {}\n", tx_hash(DYNAMIC_FEE))));
+        assert!(output.contains("max_fee_per_gas: 1000000000\n"));
+        assert!(output.contains(
+            "access_list: \n    0x0000000000000000000000000000000000000001\n        0x0000000000000000000000000000000000000000000000000000000000000000\n"
+        ));
+    }
+
+    #[test]
+    fn inspect_json() {
+        let output = run_with(&["--json", DYNAMIC_FEE], &[]).unwrap();
+        let output: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(output["type"], 2);
+        assert_eq!(output["hash"], tx_hash(DYNAMIC_FEE));
+        assert_eq!(output["round_trip"], true);
+
+        let fields = &output["fields"];
+        assert_eq!(fields["chain_id"], "0x1");
+        assert_eq!(fields["nonce"], "0x0");
+        assert_eq!(fields["gas_limit"], "0x1e241");
+        assert_eq!(
+            fields["access_list"][0]["address"],
+            "0x0000000000000000000000000000000000000001"
+        );
+        assert_eq!(
+            fields["access_list"][0]["storage_keys"][0],
+            format!("0x{}", "0".repeat(64))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let binary = hex::decode(LEGACY).unwrap();
+        assert!(run_with(&[], &binary).is_ok());
+
+        let tests: [(&[&str], u8); 8] = [
+            // truncated
+            (&[&LEGACY[..LEGACY.len() - 2]], 1),
+            // 33 bytes storage key
+            (&["02f8a2018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f83af838940000000000000000000000000000000000000001e2a101000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8"], 1),
+            // unknown type
+            (&["0x05c0"], 1),
+            (&["0xzz"], 1),
+            (&["0x"], 1),
+            (&["-"], 1),
+            (&["no_such_file.bin"], 3),
+            (&["--yaml", LEGACY], 2),
+        ];
+
+        for (i, (args, code)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            let error = run_with(args, &[]).unwrap_err();
+            assert_eq!(error.exit_code(), code);
+
+            println!("ok");
+        }
+    }
+}
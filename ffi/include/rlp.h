@@ -0,0 +1,160 @@
+#ifndef RLP_H
+#define RLP_H
+
+/* Generated by cbindgen from the rlp-ffi crate, do not edit. Regenerate with `UPDATE_HEADER=1 cargo test -p rlp-ffi`. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of a function, the errors mirroring `RlpError`.
+typedef enum RlpStatus {
+  RLP_STATUS_OK = 0,
+  RLP_STATUS_MISSING_BYTES = 1,
+  RLP_STATUS_TRAILING_BYTES = 2,
+  RLP_STATUS_EXPECTED_BYTES = 3,
+  RLP_STATUS_EXPECTED_LIST = 4,
+  RLP_STATUS_INVALID_BYTES = 5,
+  RLP_STATUS_INVALID_LENGTH = 6,
+  // Any other decoding error, described by `rlp_last_error_message`
+  RLP_STATUS_MESSAGE = 7,
+  // A pointer argument is null
+  RLP_STATUS_NULL_POINTER = 8,
+  // An index past the end of a list
+  RLP_STATUS_OUT_OF_BOUNDS = 9,
+  // A bug of this library, which panicked
+  RLP_STATUS_PANIC = 10,
+} RlpStatus;
+
+// A decoded block.
+typedef struct RlpBlock RlpBlock;
+
+// A decoded RLP item, a string or a list.
+typedef struct RlpItem RlpItem;
+
+// A decoded signed transaction.
+typedef struct RlpTransaction RlpTransaction;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// The message of the last error returned on this thread, or null.
+// The string is valid until the next call to a function of this API on the thread.
+const char *rlp_last_error_message(void);
+
+// Decode the single item of `len` bytes at `bytes` into `*out`, to free with `rlp_item_free`.
+//
+// # Safety
+//
+// `bytes` must point to `len` readable bytes and `out` to a writable pointer.
+enum RlpStatus rlp_decode(const uint8_t *bytes, size_t len, struct RlpItem **out);
+
+// # Safety
+//
+// `item` must be null or returned by this API and not freed yet.
+void rlp_item_free(struct RlpItem *item);
+
+// Whether the item is a list, false for a null `item`.
+//
+// # Safety
+//
+// `item` must be null or a live item.
+bool rlp_item_is_list(const struct RlpItem *item);
+
+// The number of items of a list, or of bytes of a string, 0 for a null `item`.
+//
+// # Safety
+//
+// `item` must be null or a live item.
+size_t rlp_item_len(const struct RlpItem *item);
+
+// The bytes of a string, valid as long as `item`.
+//
+// # Safety
+//
+// `item` must be a live item, `data` and `len` writable pointers.
+enum RlpStatus rlp_item_bytes(const struct RlpItem *item, const uint8_t **data, size_t *len);
+
+// Copy the item at `index` of a list into `*out`, to free with `rlp_item_free`.
+//
+// # Safety
+//
+// `item` must be a live item and `out` a writable pointer.
+enum RlpStatus rlp_item_get(const struct RlpItem *item, size_t index, struct RlpItem **out);
+
+// Decode a raw transaction, or one wrapped in a string as in a block body, into `*out`,
+// to free with `rlp_transaction_free`.
+//
+// # Safety
+//
+// `bytes` must point to `len` readable bytes and `out` to a writable pointer.
+enum RlpStatus rlp_transaction_decode(const uint8_t *bytes,
+                                      size_t len,
+                                      struct RlpTransaction **out);
+
+// # Safety
+//
+// `tx` must be null or returned by this API and not freed yet.
+void rlp_transaction_free(struct RlpTransaction *tx);
+
+// The EIP-2718 type of the transaction, 0 for legacy transactions,
+// and 0xff, which is not a valid type, for a null `tx`.
+//
+// # Safety
+//
+// `tx` must be null or a live transaction.
+uint8_t rlp_transaction_type(const struct RlpTransaction *tx);
+
+// # Safety
+//
+// `tx` must be a live transaction and `out` point to 32 writable bytes.
+enum RlpStatus rlp_transaction_hash(const struct RlpTransaction *tx, uint8_t (*out)[32]);
+
+// Decode a block into `*out`, to free with `rlp_block_free`.
+//
+// # Safety
+//
+// `bytes` must point to `len` readable bytes and `out` to a writable pointer.
+enum RlpStatus rlp_block_decode(const uint8_t *bytes, size_t len, struct RlpBlock **out);
+
+// # Safety
+//
+// `block` must be null or returned by this API and not freed yet.
+void rlp_block_free(struct RlpBlock *block);
+
+// # Safety
+//
+// `block` must be a live block and `out` point to 32 writable bytes.
+enum RlpStatus rlp_block_hash(const struct RlpBlock *block, uint8_t (*out)[32]);
+
+// The number of transactions, 0 for a null `block`.
+//
+// # Safety
+//
+// `block` must be null or a live block.
+size_t rlp_block_transactions_len(const struct RlpBlock *block);
+
+// Copy the transaction at `index` into `*out`, to free with `rlp_transaction_free`.
+//
+// # Safety
+//
+// `block` must be a live block and `out` a writable pointer.
+enum RlpStatus rlp_block_transaction(const struct RlpBlock *block,
+                                     size_t index,
+                                     struct RlpTransaction **out);
+
+// The number of uncles, 0 for a null `block`.
+//
+// # Safety
+//
+// `block` must be null or a live block.
+size_t rlp_block_uncles_len(const struct RlpBlock *block);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RLP_H */
@@ -0,0 +1,580 @@
+//! C API over `rlp-rs` and `rlp-types`, declared in the generated header `include/rlp.h`.
+//!
+//! Decoded values are opaque handles owned by the caller and released with their `_free` function.
+//! Fallible functions return an `RlpStatus` and write their result through an out pointer,
+//! the message of the last error of the thread is returned by `rlp_last_error_message`.
+
+use rlp_rs::{unpack_rlp, RecursiveBytes, RlpError};
+use rlp_types::{Block, TransactionEnvelope};
+use std::cell::RefCell;
+use std::ffi::{c_char, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice};
+
+/// Result of a function, the errors mirroring `RlpError`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RlpStatus {
+    Ok = 0,
+    MissingBytes = 1,
+    TrailingBytes = 2,
+    ExpectedBytes = 3,
+    ExpectedList = 4,
+    InvalidBytes = 5,
+    InvalidLength = 6,
+    /// Any other decoding error, described by `rlp_last_error_message`
+    Message = 7,
+    /// A pointer argument is null
+    NullPointer = 8,
+    /// An index past the end of a list
+    OutOfBounds = 9,
+    /// A bug of this library, which panicked
+    Panic = 10,
+}
+
+/// A decoded RLP item, a string or a list.
+pub struct RlpItem(RecursiveBytes);
+
+/// A decoded signed transaction.
+pub struct RlpTransaction(TransactionEnvelope);
+
+/// A decoded block.
+pub struct RlpBlock(Block);
+
+enum Error {
+    Rlp(RlpError),
+    NullPointer,
+    OutOfBounds { index: usize, len: usize },
+    Panic(String),
+}
+
+impl From<RlpError> for Error {
+    fn from(error: RlpError) -> Self {
+        Error::Rlp(error)
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// the status of `f`, recording the message of its error for `rlp_last_error_message`.
+/// A panic is caught rather than unwinding into the caller.
+fn run(f: impl FnOnce() -> Result<(), Error>) -> RlpStatus {
+    let error = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return RlpStatus::Ok,
+        Ok(Err(error)) => error,
+        Err(payload) => {
+            let message = match (
+                payload.downcast_ref::<&str>(),
+                payload.downcast_ref::<String>(),
+            ) {
+                (Some(message), _) => message.to_string(),
+                (_, Some(message)) => message.clone(),
+                _ => String::from("unknown panic"),
+            };
+            Error::Panic(message)
+        }
+    };
+
+    let (status, message) = match error {
+        Error::Rlp(error) => {
+            let status = match error {
+                RlpError::MissingBytes => RlpStatus::MissingBytes,
+                RlpError::TrailingBytes => RlpStatus::TrailingBytes,
+                RlpError::ExpectedBytes => RlpStatus::ExpectedBytes,
+                RlpError::ExpectedList => RlpStatus::ExpectedList,
+                RlpError::InvalidBytes => RlpStatus::InvalidBytes,
+                RlpError::InvalidLength => RlpStatus::InvalidLength,
+                RlpError::Message(_) => RlpStatus::Message,
+            };
+            (status, error.to_string())
+        }
+        Error::NullPointer => (RlpStatus::NullPointer, String::from("null pointer")),
+        Error::OutOfBounds { index, len } => (
+            RlpStatus::OutOfBounds,
+            format!("index {index} out of bounds of a list of {len} items"),
+        ),
+        Error::Panic(message) => (RlpStatus::Panic, format!("panicked: {message}")),
+    };
+    // messages come from Display implementations, without NUL bytes
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+
+    status
+}
+
+/// `len` bytes at `bytes`, which may be null when `len` is 0.
+unsafe fn input<'a>(bytes: *const u8, len: usize) -> Result<&'a [u8], Error> {
+    match (bytes.is_null(), len) {
+        (true, 0) => Ok(&[]),
+        (true, _) => Err(Error::NullPointer),
+        (false, _) => Ok(slice::from_raw_parts(bytes, len)),
+    }
+}
+
+unsafe fn handle<'a, T>(handle: *const T) -> Result<&'a T, Error> {
+    handle.as_ref().ok_or(Error::NullPointer)
+}
+
+unsafe fn write<T>(out: *mut T, value: T) -> Result<(), Error> {
+    if out.is_null() {
+        return Err(Error::NullPointer);
+    }
+    out.write(value);
+    Ok(())
+}
+
+/// a new handle to `value`, allocated once `out` is known to be valid.
+unsafe fn write_handle<T>(out: *mut *mut T, value: T) -> Result<(), Error> {
+    if out.is_null() {
+        return Err(Error::NullPointer);
+    }
+    out.write(Box::into_raw(Box::new(value)));
+    Ok(())
+}
+
+unsafe fn free<T>(handle: *mut T) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// The message of the last error returned on this thread, or null.
+/// The string is valid until the next call to a function of this API on the thread.
+#[no_mangle]
+pub extern "C" fn rlp_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Decode the single item of `len` bytes at `bytes` into `*out`, to free with `rlp_item_free`.
+///
+/// # Safety
+///
+/// `bytes` must point to `len` readable bytes and `out` to a writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_decode(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut RlpItem,
+) -> RlpStatus {
+    run(|| {
+        let mut rlp = unpack_rlp(input(bytes, len)?)?;
+        if rlp.len() > 1 {
+            return Err(RlpError::TrailingBytes.into());
+        }
+        let item = rlp.pop_front().ok_or(RlpError::MissingBytes)?;
+        write_handle(out, RlpItem(item))
+    })
+}
+
+/// # Safety
+///
+/// `item` must be null or returned by this API and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_item_free(item: *mut RlpItem) {
+    free(item)
+}
+
+/// Whether the item is a list, false for a null `item`.
+///
+/// # Safety
+///
+/// `item` must be null or a live item.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_item_is_list(item: *const RlpItem) -> bool {
+    matches!(item.as_ref(), Some(RlpItem(RecursiveBytes::Nested(_))))
+}
+
+/// The number of items of a list, or of bytes of a string, 0 for a null `item`.
+///
+/// # Safety
+///
+/// `item` must be null or a live item.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_item_len(item: *const RlpItem) -> usize {
+    match item.as_ref().map(|item| &item.0) {
+        Some(RecursiveBytes::Bytes(bytes)) => bytes.len(),
+        Some(RecursiveBytes::Nested(items)) => items.len(),
+        Some(RecursiveBytes::EmptyList) | None => 0,
+    }
+}
+
+/// The bytes of a string, valid as long as `item`.
+///
+/// # Safety
+///
+/// `item` must be a live item, `data` and `len` writable pointers.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_item_bytes(
+    item: *const RlpItem,
+    data: *mut *const u8,
+    len: *mut usize,
+) -> RlpStatus {
+    run(|| {
+        let bytes: &[u8] = match &handle(item)?.0 {
+            RecursiveBytes::Bytes(bytes) => bytes,
+            RecursiveBytes::EmptyList => &[],
+            RecursiveBytes::Nested(_) => return Err(RlpError::ExpectedBytes.into()),
+        };
+        write(data, bytes.as_ptr())?;
+        write(len, bytes.len())
+    })
+}
+
+/// Copy the item at `index` of a list into `*out`, to free with `rlp_item_free`.
+///
+/// # Safety
+///
+/// `item` must be a live item and `out` a writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_item_get(
+    item: *const RlpItem,
+    index: usize,
+    out: *mut *mut RlpItem,
+) -> RlpStatus {
+    run(|| {
+        let items = match &handle(item)?.0 {
+            RecursiveBytes::Nested(items) => items,
+            _ => return Err(RlpError::ExpectedList.into()),
+        };
+        let len = items.len();
+        let child = items.get(index).ok_or(Error::OutOfBounds { index, len })?;
+        write_handle(out, RlpItem(child.clone()))
+    })
+}
+
+/// Decode a raw transaction, or one wrapped in a string as in a block body, into `*out`,
+/// to free with `rlp_transaction_free`.
+///
+/// # Safety
+///
+/// `bytes` must point to `len` readable bytes and `out` to a writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_transaction_decode(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut RlpTransaction,
+) -> RlpStatus {
+    run(|| {
+        let tx = TransactionEnvelope::from_raw_bytes(input(bytes, len)?)?;
+        write_handle(out, RlpTransaction(tx))
+    })
+}
+
+/// # Safety
+///
+/// `tx` must be null or returned by this API and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_transaction_free(tx: *mut RlpTransaction) {
+    free(tx)
+}
+
+/// The EIP-2718 type of the transaction, 0 for legacy transactions,
+/// and 0xff, which is not a valid type, for a null `tx`.
+///
+/// # Safety
+///
+/// `tx` must be null or a live transaction.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_transaction_type(tx: *const RlpTransaction) -> u8 {
+    tx.as_ref().map_or(0xff, |tx| tx.0.tx_type())
+}
+
+/// # Safety
+///
+/// `tx` must be a live transaction and `out` point to 32 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_transaction_hash(
+    tx: *const RlpTransaction,
+    out: *mut [u8; 32],
+) -> RlpStatus {
+    run(|| write(out, handle(tx)?.0.hash()?))
+}
+
+/// Decode a block into `*out`, to free with `rlp_block_free`.
+///
+/// # Safety
+///
+/// `bytes` must point to `len` readable bytes and `out` to a writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_block_decode(
+    bytes: *const u8,
+    len: usize,
+    out: *mut *mut RlpBlock,
+) -> RlpStatus {
+    run(|| {
+        let block = Block::from_bytes(input(bytes, len)?)?;
+        write_handle(out, RlpBlock(block))
+    })
+}
+
+/// # Safety
+///
+/// `block` must be null or returned by this API and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_block_free(block: *mut RlpBlock) {
+    free(block)
+}
+
+/// # Safety
+///
+/// `block` must be a live block and `out` point to 32 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_block_hash(block: *const RlpBlock, out: *mut [u8; 32]) -> RlpStatus {
+    run(|| write(out, handle(block)?.0.hash()?))
+}
+
+/// The number of transactions, 0 for a null `block`.
+///
+/// # Safety
+///
+/// `block` must be null or a live block.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_block_transactions_len(block: *const RlpBlock) -> usize {
+    block.as_ref().map_or(0, |block| block.0.transactions.len())
+}
+
+/// Copy the transaction at `index` into `*out`, to free with `rlp_transaction_free`.
+///
+/// # Safety
+///
+/// `block` must be a live block and `out` a writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_block_transaction(
+    block: *const RlpBlock,
+    index: usize,
+    out: *mut *mut RlpTransaction,
+) -> RlpStatus {
+    run(|| {
+        let transactions = &handle(block)?.0.transactions;
+        let len = transactions.len();
+        let tx = transactions
+            .get(index)
+            .ok_or(Error::OutOfBounds { index, len })?;
+        write_handle(out, RlpTransaction(tx.clone()))
+    })
+}
+
+/// The number of uncles, 0 for a null `block`.
+///
+/// # Safety
+///
+/// `block` must be null or a live block.
+#[no_mangle]
+pub unsafe extern "C" fn rlp_block_uncles_len(block: *const RlpBlock) -> usize {
+    block.as_ref().map_or(0, |block| block.0.uncles.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha3::{Digest, Keccak256};
+    use std::ffi::CStr;
+    use std::path::Path;
+
+    const DYNAMIC_FEE: &str = "02f8a0018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8";
+    const LONDON_BLOCK: &str = "f9030bf901fea083cafc574e1f51ba9dc0568fc617a08ea2429fb384059c972f13b19fa1c8dd55a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347948888f1f195afa192cfee860698584c030f4c9db1a0ef1552a40b7165c3cd773806b9e0c165b75356e0314bf0706f279c729f51e017a05fe50b260da6308036625b850b5d6ced6d0a9f814c0688bc91ffb7b7a3a54b67a0bc37d79753ad738a6dac4921e57392f145d8887476de3f783dfa7edae9283e52b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008302000001832fefd8825208845506eb0780a0bd4472abb6659ebe3ee06ee4d7b72a00a9f4d001caca51342001075469aff49888a13a5a8c8f2bb1c4843b9aca00f90106f85f800a82c35094095e7baea6a6c7c4c2dfeb977efac326af552d870a801ba09bea4c4daac7c7c52e093e6a4c35dbbcf8856f1af7b059ba20253e70848d094fa08a8fae537ce25ed8cb5af9adac3f141af69bd515bd2ba031522df09b97dd72b1b8a302f8a0018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8c0";
+
+    fn last_error() -> String {
+        let message = rlp_last_error_message();
+        assert!(!message.is_null());
+        unsafe { CStr::from_ptr(message) }
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    unsafe fn item_bytes(item: *const RlpItem) -> Vec<u8> {
+        let mut data = ptr::null();
+        let mut len = 0;
+        assert_eq!(rlp_item_bytes(item, &mut data, &mut len), RlpStatus::Ok);
+        input(data, len).ok().unwrap().to_vec()
+    }
+
+    #[test]
+    fn decode_item() {
+        // [ "cat", [ "dog", "" ] ]
+        let bytes = hex::decode("ca83636174c583646f6780").unwrap();
+        unsafe {
+            let mut item = ptr::null_mut();
+            assert_eq!(
+                rlp_decode(bytes.as_ptr(), bytes.len(), &mut item),
+                RlpStatus::Ok
+            );
+            assert!(rlp_item_is_list(item));
+            assert_eq!(rlp_item_len(item), 2);
+
+            let mut cat = ptr::null_mut();
+            assert_eq!(rlp_item_get(item, 0, &mut cat), RlpStatus::Ok);
+            assert!(!rlp_item_is_list(cat));
+            assert_eq!(rlp_item_len(cat), 3);
+            assert_eq!(item_bytes(cat), b"cat");
+
+            let mut list = ptr::null_mut();
+            assert_eq!(rlp_item_get(item, 1, &mut list), RlpStatus::Ok);
+            let mut empty = ptr::null_mut();
+            assert_eq!(rlp_item_get(list, 1, &mut empty), RlpStatus::Ok);
+            assert_eq!(rlp_item_len(empty), 0);
+            assert_eq!(item_bytes(empty), b"");
+
+            let mut out = ptr::null_mut();
+            assert_eq!(rlp_item_get(item, 2, &mut out), RlpStatus::OutOfBounds);
+            assert_eq!(last_error(), "index 2 out of bounds of a list of 2 items");
+            assert_eq!(rlp_item_get(cat, 0, &mut out), RlpStatus::ExpectedList);
+            assert!(out.is_null());
+            let (mut data, mut len) = (ptr::null(), 0);
+            assert_eq!(
+                rlp_item_bytes(list, &mut data, &mut len),
+                RlpStatus::ExpectedBytes
+            );
+
+            for handle in [item, cat, list, empty] {
+                rlp_item_free(handle);
+            }
+            rlp_item_free(ptr::null_mut());
+
+            assert!(!rlp_item_is_list(ptr::null()));
+            assert_eq!(rlp_item_len(ptr::null()), 0);
+        }
+    }
+
+    #[test]
+    fn decode_errors() {
+        let tests = [
+            ("", RlpStatus::MissingBytes),
+            ("8100", RlpStatus::InvalidBytes),
+            ("b80180", RlpStatus::InvalidLength),
+            ("c28200", RlpStatus::MissingBytes),
+            ("8080", RlpStatus::TrailingBytes),
+        ];
+
+        for (i, (bytes, status)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            let bytes = hex::decode(bytes).unwrap();
+            let mut item = ptr::null_mut();
+            let result = unsafe { rlp_decode(bytes.as_ptr(), bytes.len(), &mut item) };
+            assert_eq!(result, status);
+            assert!(item.is_null());
+            assert!(!last_error().is_empty());
+
+            println!("ok");
+        }
+
+        unsafe {
+            let mut item = ptr::null_mut();
+            assert_eq!(
+                rlp_decode(ptr::null(), 1, &mut item),
+                RlpStatus::NullPointer
+            );
+            assert_eq!(last_error(), "null pointer");
+            assert_eq!(
+                rlp_decode([0x80].as_ptr(), 1, ptr::null_mut()),
+                RlpStatus::NullPointer
+            );
+        }
+    }
+
+    #[test]
+    fn transaction() {
+        let bytes = hex::decode(DYNAMIC_FEE).unwrap();
+        unsafe {
+            let mut tx = ptr::null_mut();
+            assert_eq!(
+                rlp_transaction_decode(bytes.as_ptr(), bytes.len(), &mut tx),
+                RlpStatus::Ok
+            );
+            assert_eq!(rlp_transaction_type(tx), 2);
+
+            let mut hash = [0; 32];
+            assert_eq!(rlp_transaction_hash(tx, &mut hash), RlpStatus::Ok);
+            assert_eq!(hash, <[u8; 32]>::from(Keccak256::digest(&bytes)));
+            rlp_transaction_free(tx);
+
+            let mut tx = ptr::null_mut();
+            assert_eq!(
+                rlp_transaction_decode([0x05, 0xc0].as_ptr(), 2, &mut tx),
+                RlpStatus::InvalidBytes
+            );
+            assert!(tx.is_null());
+
+            assert_eq!(rlp_transaction_type(ptr::null()), 0xff);
+        }
+    }
+
+    #[test]
+    fn block() {
+        let bytes = hex::decode(LONDON_BLOCK).unwrap();
+        unsafe {
+            let mut block = ptr::null_mut();
+            assert_eq!(
+                rlp_block_decode(bytes.as_ptr(), bytes.len(), &mut block),
+                RlpStatus::Ok
+            );
+            assert_eq!(rlp_block_transactions_len(block), 2);
+            assert_eq!(rlp_block_uncles_len(block), 0);
+
+            let mut hash = [0; 32];
+            assert_eq!(rlp_block_hash(block, &mut hash), RlpStatus::Ok);
+            assert_eq!(hash, Block::from_bytes(&bytes).unwrap().hash().unwrap());
+
+            let tx_bytes = hex::decode(DYNAMIC_FEE).unwrap();
+            let mut tx = ptr::null_mut();
+            assert_eq!(rlp_block_transaction(block, 1, &mut tx), RlpStatus::Ok);
+            assert_eq!(rlp_transaction_type(tx), 2);
+            assert_eq!(rlp_transaction_hash(tx, &mut hash), RlpStatus::Ok);
+            assert_eq!(hash, <[u8; 32]>::from(Keccak256::digest(&tx_bytes)));
+            rlp_transaction_free(tx);
+
+            let mut tx = ptr::null_mut();
+            assert_eq!(
+                rlp_block_transaction(block, 2, &mut tx),
+                RlpStatus::OutOfBounds
+            );
+            rlp_block_free(block);
+
+            let mut block = ptr::null_mut();
+            assert_eq!(
+                rlp_block_decode(bytes.as_ptr(), bytes.len() - 1, &mut block),
+                RlpStatus::MissingBytes
+            );
+            assert!(block.is_null());
+
+            assert_eq!(rlp_block_transactions_len(ptr::null()), 0);
+            assert_eq!(rlp_block_uncles_len(ptr::null()), 0);
+        }
+    }
+
+    #[test]
+    fn panic() {
+        let status = run(|| panic!("boom"));
+        assert_eq!(status, RlpStatus::Panic);
+        assert_eq!(last_error(), "panicked: boom");
+
+        let status = run(|| panic!("{} {}", "formatted", "boom"));
+        assert_eq!(status, RlpStatus::Panic);
+        assert_eq!(last_error(), "panicked: formatted boom");
+    }
+
+    #[test]
+    fn header_up_to_date() {
+        let crate_dir = env!("CARGO_MANIFEST_DIR");
+        let mut header = Vec::new();
+        cbindgen::generate(crate_dir).unwrap().write(&mut header);
+
+        let path = Path::new(crate_dir).join("include/rlp.h");
+        if std::env::var_os("UPDATE_HEADER").is_some() {
+            std::fs::write(&path, &header).unwrap();
+        }
+        let committed = std::fs::read(&path).unwrap_or_default();
+        assert!(
+            committed == header,
+            "include/rlp.h is out of date, regenerate it with `UPDATE_HEADER=1 cargo test -p rlp-ffi`"
+        );
+    }
+}
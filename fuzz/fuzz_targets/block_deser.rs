@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::{fuzz_target, Corpus};
+use rlp_types::Block;
+
+fuzz_target!(|bytes: &[u8]| -> Corpus {
+    let _ = Block::unknown_from_bytes(bytes);
+    let block = match Block::from_bytes(bytes) {
+        Ok(block) => block,
+        Err(_) => return Corpus::Reject,
+    };
+    let serialized = rlp_rs::to_bytes(&block).unwrap();
+    assert_eq!(bytes, serialized);
+    assert_eq!(block.hash(), Block::from_bytes(&serialized).unwrap().hash());
+    Corpus::Keep
+});
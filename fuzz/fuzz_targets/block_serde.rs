@@ -0,0 +1,22 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rlp_types::{Block, Header};
+
+fuzz_target!(|block: Block| {
+    let bytes = rlp_rs::to_bytes(&block).unwrap();
+    let unknown = core::iter::once(&block.header)
+        .chain(&block.uncles)
+        .any(|header| matches!(header, Header::Unknown { .. }));
+
+    let decoded = match unknown {
+        // every header is decoded as `Unknown`, compare the encodings instead
+        true => Block::unknown_from_bytes(&bytes).unwrap(),
+        false => Block::from_bytes(&bytes).unwrap(),
+    };
+    if !unknown {
+        assert_eq!(block, decoded);
+    }
+    assert_eq!(bytes, rlp_rs::to_bytes(&decoded).unwrap());
+    assert_eq!(block.hash().unwrap(), decoded.hash().unwrap());
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::{arbitrary::Unstructured, fuzz_target};
+use rlp_rs_fuzz::differential::{check_decode, check_decode_u64, check_encode};
+
+fuzz_target!(|bytes: &[u8]| {
+    check_decode(bytes);
+    check_decode_u64(bytes);
+    let _ = check_encode(&mut Unstructured::new(bytes));
+});
@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::{fuzz_target, Corpus};
+use rlp_rs::unpack_rlp;
+use rlp_types::Header;
+
+fuzz_target!(|bytes: &[u8]| -> Corpus {
+    let rlp = match unpack_rlp(bytes) {
+        Ok(rlp) => rlp,
+        Err(_) => return Corpus::Reject,
+    };
+    let _ = Header::unknown_from_raw_rlp(rlp.clone());
+    let _ = rlp_rs::from_bytes::<Header>(bytes);
+    let header = match Header::from_raw_rlp(rlp) {
+        Ok(header) => header,
+        Err(_) => return Corpus::Keep,
+    };
+    let serialized = rlp_rs::to_bytes(&header).unwrap();
+    assert_eq!(bytes, serialized);
+    Corpus::Keep
+});
@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rlp_rs::unpack_rlp;
+use rlp_types::Header;
+
+fuzz_target!(|header: Header| {
+    let bytes = rlp_rs::to_bytes(&header).unwrap();
+    let decoded = match header {
+        // the extra fields are only kept apart by `unknown_from_raw_rlp`
+        Header::Unknown { .. } => Header::unknown_from_raw_rlp(unpack_rlp(&bytes).unwrap()),
+        _ => Header::from_raw_rlp(unpack_rlp(&bytes).unwrap()),
+    };
+    assert_eq!(header, decoded.unwrap());
+
+    if !matches!(header, Header::Unknown { .. }) {
+        let deserialized: Header = rlp_rs::from_bytes(&bytes).unwrap();
+        assert_eq!(header, deserialized);
+    }
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rlp_rs_fuzz::rich::check_round_trip;
+
+fuzz_target!(|bytes: &[u8]| {
+    check_round_trip(bytes);
+});
@@ -0,0 +1,232 @@
+//! Differential checks of rlp-rs against the `rlp` crate from parity.
+
+use libfuzzer_sys::arbitrary::{self, Arbitrary, Unstructured};
+use rlp::{DecoderError, RlpStream};
+use rlp_rs::{pack_rlp, unpack_rlp, RecursiveBytes, Rlp};
+
+/// Decoded item, independent of both implementations.
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary)]
+pub enum Tree {
+    Bytes(Vec<u8>),
+    List(Vec<Tree>),
+}
+
+impl Tree {
+    fn from_rlp_rs(rec: RecursiveBytes) -> Self {
+        match rec {
+            RecursiveBytes::Bytes(bytes) => Tree::Bytes(bytes.into_vec()),
+            // serialized as an empty string despite its name
+            RecursiveBytes::EmptyList => Tree::Bytes(vec![]),
+            RecursiveBytes::Nested(recs) => {
+                Tree::List(recs.into_iter().map(Tree::from_rlp_rs).collect())
+            }
+        }
+    }
+
+    fn into_rlp_rs(self) -> RecursiveBytes {
+        match self {
+            Tree::Bytes(bytes) => RecursiveBytes::Bytes(bytes.into()),
+            Tree::List(trees) => {
+                RecursiveBytes::Nested(trees.into_iter().map(Tree::into_rlp_rs).collect())
+            }
+        }
+    }
+
+    /// decode a single item with parity, requiring every list to be filled exactly by its items,
+    /// which its lazy list iterator doesn't check.
+    fn from_parity(rlp: &rlp::Rlp) -> Result<Self, DecoderError> {
+        if !rlp.is_list() {
+            return rlp
+                .decoder()
+                .decode_value(|bytes| Ok(Tree::Bytes(bytes.to_vec())));
+        }
+
+        let info = rlp.payload_info()?;
+        let mut trees = Vec::new();
+        let mut consumed = 0;
+        for i in 0..rlp.item_count()? {
+            let item = rlp.at(i)?;
+            consumed += item.as_raw().len();
+            trees.push(Tree::from_parity(&item)?);
+        }
+        if consumed != info.value_len {
+            return Err(DecoderError::RlpInconsistentLengthAndData);
+        }
+        Ok(Tree::List(trees))
+    }
+
+    fn append_parity(&self, stream: &mut RlpStream) {
+        match self {
+            Tree::Bytes(bytes) => {
+                stream.append(&bytes.as_slice());
+            }
+            Tree::List(trees) => {
+                stream.begin_list(trees.len());
+                for tree in trees {
+                    tree.append_parity(stream);
+                }
+            }
+        }
+    }
+}
+
+/// The items of `bytes` decoded by rlp-rs.
+pub fn decode_rlp_rs(bytes: &[u8]) -> Option<Vec<Tree>> {
+    let mut rlp = unpack_rlp(bytes).ok()?;
+    Some(
+        core::iter::from_fn(|| rlp.pop_front())
+            .map(Tree::from_rlp_rs)
+            .collect(),
+    )
+}
+
+/// The items of `bytes` decoded by parity, which decodes one item at a time.
+/// Like a single item, a sequence can't be empty.
+pub fn decode_parity(mut bytes: &[u8]) -> Option<Vec<Tree>> {
+    let mut trees = Vec::new();
+    loop {
+        let len = rlp::Rlp::new(bytes).payload_info().ok()?.total();
+        let item = bytes.get(..len)?;
+        trees.push(Tree::from_parity(&rlp::Rlp::new(item)).ok()?);
+        bytes = &bytes[len..];
+        if bytes.is_empty() {
+            return Some(trees);
+        }
+    }
+}
+
+pub fn encode_rlp_rs(trees: &[Tree]) -> Vec<u8> {
+    let recs = trees.iter().cloned().map(Tree::into_rlp_rs).collect();
+    pack_rlp(Rlp::new(recs)).unwrap()
+}
+
+pub fn encode_parity(trees: &[Tree]) -> Vec<u8> {
+    let mut stream = RlpStream::new();
+    for tree in trees {
+        tree.append_parity(&mut stream);
+    }
+    stream.out().to_vec()
+}
+
+/// Both implementations accept the same inputs and decode them to the same items.
+pub fn check_decode(bytes: &[u8]) {
+    let ours = decode_rlp_rs(bytes);
+    let theirs = decode_parity(bytes);
+    assert_eq!(ours, theirs, "decoding 0x{}", hex(bytes));
+
+    if let Some(trees) = ours {
+        assert_eq!(encode_rlp_rs(&trees), bytes);
+        assert_eq!(encode_parity(&trees), bytes);
+    }
+}
+
+/// Both implementations encode the same values to the same bytes.
+pub fn check_encode(u: &mut Unstructured) -> arbitrary::Result<()> {
+    let trees: Vec<Tree> = u.arbitrary()?;
+    assert_eq!(encode_rlp_rs(&trees), encode_parity(&trees), "{trees:?}");
+
+    let number: u64 = u.arbitrary()?;
+    assert_eq!(
+        rlp_rs::to_bytes(&number).unwrap(),
+        rlp::encode(&number).to_vec()
+    );
+
+    let boolean: bool = u.arbitrary()?;
+    assert_eq!(
+        rlp_rs::to_bytes(&boolean).unwrap(),
+        rlp::encode(&boolean).to_vec()
+    );
+
+    let text: String = u.arbitrary()?;
+    assert_eq!(
+        rlp_rs::to_bytes(&text).unwrap(),
+        rlp::encode(&text).to_vec()
+    );
+
+    let numbers: Vec<u64> = u.arbitrary()?;
+    assert_eq!(
+        rlp_rs::to_bytes(&numbers).unwrap(),
+        rlp::encode_list(&numbers).to_vec()
+    );
+    Ok(())
+}
+
+/// Both implementations accept the same encodings of a `u64`.
+pub fn check_decode_u64(bytes: &[u8]) {
+    let ours = rlp_rs::from_bytes::<u64>(bytes).ok();
+    let theirs = match rlp::Rlp::new(bytes).payload_info() {
+        Ok(info) if info.total() == bytes.len() => rlp::decode::<u64>(bytes).ok(),
+        _ => None,
+    };
+    assert_eq!(ours, theirs, "decoding u64 0x{}", hex(bytes));
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_decode, check_decode_u64, check_encode, decode_rlp_rs, Tree};
+    use libfuzzer_sys::arbitrary::Unstructured;
+
+    /// Inputs on which the implementations diverged, or that the harness got wrong,
+    /// and edge cases of the spec. Add any input reported by the `differential` target here.
+    const REGRESSIONS: [&str; 17] = [
+        // an empty input is not an item
+        "",
+        // `RecursiveBytes::EmptyList` is the empty string, not `c0`
+        "c004c060",
+        "80",
+        "c0",
+        // single bytes below 0x80 must not have a prefix
+        "8100",
+        "817f",
+        "8180",
+        // long form for a short length
+        "b80100",
+        "f80100",
+        // leading zero in a long length, and the shortest long length
+        "b900380000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        "b8380000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        // an item overflowing its list
+        "c2820000",
+        "c30102",
+        // a list with bytes left over after its items
+        "c3c10102",
+        // length of length overflowing a usize
+        "bfffffffffffffffffff",
+        // several items
+        "0102c0",
+        "c88363617483646f67",
+    ];
+
+    #[test]
+    fn regressions() {
+        for (i, input) in REGRESSIONS.into_iter().enumerate() {
+            println!("{i}...");
+
+            let bytes = hex::decode(input).unwrap();
+            check_decode(&bytes);
+            check_decode_u64(&bytes);
+            let _ = check_encode(&mut Unstructured::new(&bytes));
+
+            println!("ok");
+        }
+    }
+
+    #[test]
+    fn decode_tree() {
+        let bytes = hex::decode("c88363617483646f6780").unwrap();
+        assert_eq!(
+            decode_rlp_rs(&bytes),
+            Some(vec![
+                Tree::List(vec![
+                    Tree::Bytes(b"cat".to_vec()),
+                    Tree::Bytes(b"dog".to_vec())
+                ]),
+                Tree::Bytes(vec![]),
+            ])
+        );
+    }
+}
@@ -0,0 +1,4 @@
+//! Checks shared by the fuzz targets and their regression tests.
+
+pub mod differential;
+pub mod rich;
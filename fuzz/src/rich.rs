@@ -0,0 +1,146 @@
+//! A type covering most of the serde data model supported by rlp-rs,
+//! to check that typed values survive a round trip.
+
+use libfuzzer_sys::arbitrary::{self, Arbitrary, Unstructured};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, Serialize, Deserialize)]
+pub struct Rich {
+    pub small: u8,
+    pub short: u16,
+    pub word: u32,
+    pub long: u64,
+    pub wide: u128,
+    pub flag: bool,
+    pub letter: char,
+    #[serde(with = "rlp_rs::signed::zigzag")]
+    pub delta: i8,
+    #[serde(with = "rlp_rs::signed::zigzag")]
+    pub offset: i32,
+    #[serde(with = "rlp_rs::signed::twos_complement")]
+    pub balance: i64,
+    #[serde(with = "rlp_rs::signed::twos_complement")]
+    pub total: i128,
+    pub text: String,
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+    pub numbers: Vec<u32>,
+    pub fixed: [u16; 3],
+    pub pair: (u8, u64),
+    pub unit: (),
+    pub inner: Inner,
+    pub nested: Vec<Inner>,
+    pub choice: Choice,
+    pub choices: Vec<Choice>,
+    pub tail: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, Serialize, Deserialize)]
+pub struct Inner {
+    pub id: u64,
+    #[serde(with = "serde_bytes")]
+    pub data: Vec<u8>,
+    pub triple: (u16, (u32, bool), String),
+    pub lists: Vec<Vec<u8>>,
+    pub wrapped: Wrapped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, Serialize, Deserialize)]
+pub struct Wrapped(pub u32, #[serde(with = "serde_bytes")] pub Vec<u8>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Arbitrary, Serialize, Deserialize)]
+pub enum Choice {
+    Empty,
+    Number(u64),
+    Text(String),
+    Numbers(Vec<u16>),
+    Pair(u8, String),
+    Record { id: u32, values: Vec<u64> },
+    Boxed(Box<Inner>),
+}
+
+/// Decoding arbitrary bytes doesn't panic, and an arbitrary value survives a round trip.
+pub fn check_round_trip(bytes: &[u8]) {
+    let _ = rlp_rs::from_bytes::<Rich>(bytes);
+
+    let value: Rich = match Unstructured::new(bytes).arbitrary() {
+        Ok(value) => value,
+        Err(_) => return,
+    };
+    let encoded = rlp_rs::to_bytes(&value).unwrap();
+    let decoded: Rich = rlp_rs::from_bytes(&encoded).unwrap();
+    assert_eq!(value, decoded);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_round_trip, Choice, Inner, Rich, Wrapped};
+
+    fn inner(id: u64) -> Inner {
+        Inner {
+            id,
+            data: vec![],
+            triple: (0, (u32::MAX, false), String::new()),
+            lists: vec![vec![], vec![0, 1]],
+            wrapped: Wrapped(0, vec![0x80]),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let choices = [
+            Choice::Empty,
+            Choice::Number(0),
+            Choice::Text(String::from("cat")),
+            Choice::Numbers(vec![]),
+            Choice::Numbers(vec![1, 0x100]),
+            Choice::Pair(0x7f, String::new()),
+            Choice::Record {
+                id: 1,
+                values: vec![0, u64::MAX],
+            },
+            Choice::Boxed(Box::new(inner(2))),
+        ];
+
+        for (i, choice) in choices.iter().enumerate() {
+            println!("{i}...");
+
+            let value = Rich {
+                small: 0,
+                short: 0x80,
+                word: 1 << 24,
+                long: u64::MAX,
+                wide: u128::MAX,
+                flag: true,
+                letter: 'é',
+                delta: -1,
+                offset: i32::MIN,
+                balance: -1,
+                total: i128::MIN,
+                text: String::from("dog"),
+                bytes: vec![0; 56],
+                numbers: vec![0, 1, 0x80],
+                fixed: [0, 1, u16::MAX],
+                pair: (1, 0),
+                unit: (),
+                inner: inner(0),
+                nested: vec![inner(1), inner(u64::MAX)],
+                choice: choice.clone(),
+                choices: choices.to_vec(),
+                tail: 0xffff,
+            };
+            let encoded = rlp_rs::to_bytes(&value).unwrap();
+            assert_eq!(rlp_rs::from_bytes::<Rich>(&encoded).unwrap(), value);
+
+            println!("ok");
+        }
+    }
+
+    #[test]
+    fn arbitrary_inputs() {
+        for len in [0, 1, 64, 512, 4096] {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 37 + 11) as u8).collect();
+            check_round_trip(&bytes);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+from typing import List, Optional, Tuple, Union
+
+Item = Union[bytes, List["Item"]]
+
+class RlpError(ValueError): ...
+
+def decode_rlp(data: bytes) -> Item:
+    """Decode a single RLP item into nested lists of `bytes`."""
+
+class Transaction:
+    @staticmethod
+    def from_bytes(data: bytes) -> "Transaction":
+        """Decode a raw transaction, or one wrapped in a string as in a block body."""
+    def to_bytes(self) -> bytes: ...
+    def hash(self) -> bytes: ...
+    @property
+    def tx_type(self) -> int: ...
+    @property
+    def chain_id(self) -> Optional[int]: ...
+    @property
+    def nonce(self) -> int: ...
+    @property
+    def gas_price(self) -> Optional[int]: ...
+    @property
+    def max_priority_fee_per_gas(self) -> Optional[int]: ...
+    @property
+    def max_fee_per_gas(self) -> Optional[int]: ...
+    @property
+    def gas_limit(self) -> int: ...
+    @property
+    def to(self) -> bytes: ...
+    @property
+    def value(self) -> int: ...
+    @property
+    def data(self) -> bytes: ...
+    @property
+    def access_list(self) -> Optional[List[Tuple[bytes, List[bytes]]]]: ...
+    @property
+    def max_fee_per_blob_gas(self) -> Optional[int]: ...
+    @property
+    def blob_hashes(self) -> Optional[List[bytes]]: ...
+    @property
+    def v(self) -> Optional[int]: ...
+    @property
+    def y_parity(self) -> Optional[int]: ...
+    @property
+    def r(self) -> int: ...
+    @property
+    def s(self) -> int: ...
+
+class Header:
+    @staticmethod
+    def from_bytes(data: bytes) -> "Header": ...
+    def to_bytes(self) -> bytes: ...
+    def hash(self) -> bytes: ...
+    @property
+    def fork(self) -> str: ...
+    @property
+    def parent_hash(self) -> bytes: ...
+    @property
+    def uncle_hash(self) -> bytes: ...
+    @property
+    def coinbase(self) -> bytes: ...
+    @property
+    def state_root(self) -> bytes: ...
+    @property
+    def tx_root(self) -> bytes: ...
+    @property
+    def receipt_hash(self) -> bytes: ...
+    @property
+    def bloom(self) -> bytes: ...
+    @property
+    def difficulty(self) -> int: ...
+    @property
+    def number(self) -> int: ...
+    @property
+    def gas_limit(self) -> int: ...
+    @property
+    def gas_used(self) -> int: ...
+    @property
+    def time(self) -> int: ...
+    @property
+    def extra(self) -> bytes: ...
+    @property
+    def mix_digest(self) -> bytes: ...
+    @property
+    def nonce(self) -> bytes: ...
+    @property
+    def base_fee(self) -> Optional[int]: ...
+    @property
+    def withdrawal_root(self) -> Optional[bytes]: ...
+    @property
+    def blob_gas_used(self) -> Optional[int]: ...
+    @property
+    def excess_blob_gas(self) -> Optional[int]: ...
+    @property
+    def parent_beacon_block_root(self) -> Optional[bytes]: ...
+
+class Block:
+    @staticmethod
+    def from_bytes(data: bytes) -> "Block": ...
+    def to_bytes(self) -> bytes: ...
+    def hash(self) -> bytes: ...
+    @property
+    def header(self) -> Header: ...
+    @property
+    def transactions(self) -> List[Transaction]: ...
+    @property
+    def uncles(self) -> List[Header]: ...
@@ -0,0 +1,468 @@
+//! Python bindings over `rlp-rs` and `rlp-types`, built with maturin as the `rlp_py` module.
+//!
+//! Strings are `bytes`, integers are Python `int`s of any size, and fields that a transaction type
+//! or a header fork doesn't have are `None`.
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyInt, PyList, PyTuple};
+use rlp_rs::{unpack_rlp, RecursiveBytes, Rlp};
+use rlp_types::{Block as RlpBlock, Field, Header as RlpHeader, TransactionEnvelope, U256};
+use sha3::{Digest, Keccak256};
+
+create_exception!(rlp_py, RlpError, PyValueError, "Invalid RLP or structure.");
+
+fn error(error: rlp_rs::RlpError) -> PyErr {
+    RlpError::new_err(error.to_string())
+}
+
+fn bytes<'py>(py: Python<'py>, bytes: impl AsRef<[u8]>) -> Bound<'py, PyBytes> {
+    PyBytes::new(py, bytes.as_ref())
+}
+
+/// a big-endian integer.
+fn int<'py>(py: Python<'py>, value: impl AsRef<[u8]>) -> PyResult<Bound<'py, PyAny>> {
+    py.get_type::<PyInt>()
+        .call_method1("from_bytes", (bytes(py, value), "big"))
+}
+
+fn optional_int<'py>(py: Python<'py>, value: Option<&U256>) -> PyResult<Option<Bound<'py, PyAny>>> {
+    value.map(|value| int(py, value)).transpose()
+}
+
+/// the single item of `data`.
+fn single_item(data: &[u8]) -> Result<RecursiveBytes, rlp_rs::RlpError> {
+    let mut rlp = unpack_rlp(data)?;
+    if rlp.len() > 1 {
+        return Err(rlp_rs::RlpError::TrailingBytes);
+    }
+    rlp.pop_front().ok_or(rlp_rs::RlpError::MissingBytes)
+}
+
+fn item<'py>(py: Python<'py>, item: &RecursiveBytes) -> PyResult<Bound<'py, PyAny>> {
+    match item {
+        RecursiveBytes::Bytes(payload) => Ok(bytes(py, payload).into_any()),
+        RecursiveBytes::EmptyList => Ok(bytes(py, []).into_any()),
+        RecursiveBytes::Nested(items) => {
+            let items = items
+                .iter()
+                .map(|rec| self::item(py, rec))
+                .collect::<PyResult<Vec<_>>>()?;
+            Ok(PyList::new(py, items)?.into_any())
+        }
+    }
+}
+
+/// Decode a single RLP item into nested lists of `bytes`.
+#[pyfunction]
+fn decode_rlp<'py>(py: Python<'py>, data: &[u8]) -> PyResult<Bound<'py, PyAny>> {
+    item(py, &single_item(data).map_err(error)?)
+}
+
+/// A transaction field as a Python object: integers are `int`s, strings `bytes`,
+/// and an access list a list of `(address, storage_keys)` tuples.
+fn field<'py>(py: Python<'py>, field: &Field) -> PyResult<Bound<'py, PyAny>> {
+    match field {
+        Field::Quantity(value) => int(py, value),
+        Field::Uint(value) => Ok(value.into_pyobject(py)?.into_any()),
+        Field::Data(value) => Ok(bytes(py, value).into_any()),
+        Field::Words(words) => {
+            Ok(PyList::new(py, words.iter().map(|word| bytes(py, word)))?.into_any())
+        }
+        Field::AccessList(access_list) => {
+            let items = access_list
+                .iter()
+                .map(|(address, keys)| {
+                    let keys = PyList::new(py, keys.iter().map(|key| bytes(py, key)))?;
+                    PyTuple::new(py, [bytes(py, address).into_any(), keys.into_any()])
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            Ok(PyList::new(py, items)?.into_any())
+        }
+    }
+}
+
+/// A signed transaction of any type.
+#[pyclass(module = "rlp_py", frozen, eq)]
+#[derive(PartialEq)]
+struct Transaction(TransactionEnvelope);
+
+impl Transaction {
+    /// The field with one of `names`, `None` when the transaction type doesn't have it.
+    fn field<'py>(&self, py: Python<'py>, names: &[&str]) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let fields = self.0.fields().map_err(error)?;
+        fields
+            .iter()
+            .find(|(name, _)| names.contains(name))
+            .map(|(_, value)| field(py, value))
+            .transpose()
+    }
+}
+
+#[pymethods]
+impl Transaction {
+    /// Decode the bytes given to `eth_sendRawTransaction`, or a transaction of a block body.
+    /// Raises `RlpError` for a storage key or a blob hash longer than 32 bytes,
+    /// so that the getters never do.
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        let tx = TransactionEnvelope::from_raw_bytes(data).map_err(error)?;
+        tx.fields().map_err(error)?;
+        Ok(Transaction(tx))
+    }
+
+    /// The raw transaction: its type followed by its fields for typed transactions.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(bytes(py, self.0.to_raw_bytes().map_err(error)?))
+    }
+
+    fn hash<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(bytes(py, self.0.hash().map_err(error)?))
+    }
+
+    #[getter]
+    fn tx_type(&self) -> u8 {
+        self.0.tx_type()
+    }
+
+    #[getter]
+    fn chain_id<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["chain_id"])
+    }
+
+    #[getter]
+    fn nonce<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["nonce"])
+    }
+
+    #[getter]
+    fn gas_price<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["gas_price"])
+    }
+
+    #[getter]
+    fn max_priority_fee_per_gas<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["max_priority_fee_per_gas"])
+    }
+
+    #[getter]
+    fn max_fee_per_gas<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["max_fee_per_gas"])
+    }
+
+    #[getter]
+    fn gas_limit<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["gas_limit"])
+    }
+
+    /// The recipient, empty for a contract creation.
+    #[getter]
+    fn to<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["to", "destination"])
+    }
+
+    #[getter]
+    fn value<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["value", "amount"])
+    }
+
+    #[getter]
+    fn data<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["data"])
+    }
+
+    /// `(address, storage_keys)` pairs.
+    #[getter]
+    fn access_list<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["access_list"])
+    }
+
+    #[getter]
+    fn max_fee_per_blob_gas<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["max_fee_per_blob_gas"])
+    }
+
+    #[getter]
+    fn blob_hashes<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["blob_hashes"])
+    }
+
+    #[getter]
+    fn v<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["v"])
+    }
+
+    #[getter]
+    fn y_parity<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["y_parity"])
+    }
+
+    #[getter]
+    fn r<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["r"])
+    }
+
+    #[getter]
+    fn s<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.field(py, &["s"])
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        let hash = self.0.hash().map_err(error)?;
+        Ok(format!(
+            "Transaction(tx_type={}, hash=0x{})",
+            self.0.tx_type(),
+            hex(&hash)
+        ))
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// A block header of any fork.
+#[pyclass(module = "rlp_py", frozen, eq)]
+#[derive(PartialEq)]
+struct Header(RlpHeader);
+
+impl Header {
+    fn encoded(&self) -> PyResult<Vec<u8>> {
+        rlp_rs::to_bytes(&self.0).map_err(error)
+    }
+}
+
+#[pymethods]
+impl Header {
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        let rlp = Rlp::new_unary(single_item(data).map_err(error)?);
+        Ok(Header(RlpHeader::from_raw_rlp(rlp).map_err(error)?))
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(bytes(py, self.encoded()?))
+    }
+
+    /// The hash of the header, which is the hash of its block.
+    fn hash<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(bytes(py, Keccak256::digest(self.encoded()?)))
+    }
+
+    /// `legacy`, `london`, `shanghai`, `cancun` or `unknown`.
+    #[getter]
+    fn fork(&self) -> &'static str {
+        match self.0 {
+            RlpHeader::Legacy { .. } => "legacy",
+            RlpHeader::London { .. } => "london",
+            RlpHeader::Shanghai { .. } => "shanghai",
+            RlpHeader::Cancun { .. } => "cancun",
+            RlpHeader::Unknown { .. } => "unknown",
+        }
+    }
+
+    #[getter]
+    fn parent_hash<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        bytes(py, self.0.parent_hash())
+    }
+
+    #[getter]
+    fn uncle_hash<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        bytes(py, self.0.uncle_hash())
+    }
+
+    #[getter]
+    fn coinbase<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        bytes(py, self.0.coinbase())
+    }
+
+    #[getter]
+    fn state_root<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        bytes(py, self.0.state_root())
+    }
+
+    #[getter]
+    fn tx_root<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        bytes(py, self.0.tx_root())
+    }
+
+    #[getter]
+    fn receipt_hash<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        bytes(py, self.0.receipt_hash())
+    }
+
+    #[getter]
+    fn bloom<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        bytes(py, self.0.bloom())
+    }
+
+    #[getter]
+    fn difficulty<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        int(py, self.0.difficulty())
+    }
+
+    #[getter]
+    fn number<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        int(py, self.0.number())
+    }
+
+    #[getter]
+    fn gas_limit(&self) -> u64 {
+        *self.0.gas_limit()
+    }
+
+    #[getter]
+    fn gas_used(&self) -> u64 {
+        *self.0.gas_used()
+    }
+
+    #[getter]
+    fn time(&self) -> u64 {
+        *self.0.time()
+    }
+
+    #[getter]
+    fn extra<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        bytes(py, self.0.extra())
+    }
+
+    #[getter]
+    fn mix_digest<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        bytes(py, self.0.mix_digest())
+    }
+
+    #[getter]
+    fn nonce<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        bytes(py, self.0.nonce())
+    }
+
+    #[getter]
+    fn base_fee<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let base_fee = match &self.0 {
+            RlpHeader::London { base_fee, .. }
+            | RlpHeader::Shanghai { base_fee, .. }
+            | RlpHeader::Cancun { base_fee, .. } => Some(base_fee),
+            _ => None,
+        };
+        optional_int(py, base_fee)
+    }
+
+    #[getter]
+    fn withdrawal_root<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyBytes>> {
+        match &self.0 {
+            RlpHeader::Shanghai {
+                withdrawal_root, ..
+            }
+            | RlpHeader::Cancun {
+                withdrawal_root, ..
+            } => Some(bytes(py, withdrawal_root)),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn blob_gas_used(&self) -> Option<u64> {
+        match self.0 {
+            RlpHeader::Cancun { blob_gas_used, .. } => Some(blob_gas_used),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn excess_blob_gas(&self) -> Option<u64> {
+        match self.0 {
+            RlpHeader::Cancun {
+                excess_blob_gas, ..
+            } => Some(excess_blob_gas),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn parent_beacon_block_root<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyBytes>> {
+        match &self.0 {
+            RlpHeader::Cancun {
+                parent_beacon_block_root,
+                ..
+            } => Some(bytes(py, parent_beacon_block_root)),
+            _ => None,
+        }
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        let hash = Keccak256::digest(self.encoded()?);
+        Ok(format!(
+            "Header(fork={:?}, hash=0x{})",
+            self.fork(),
+            hex(&hash)
+        ))
+    }
+}
+
+/// A block with its header, transactions and uncles.
+#[pyclass(module = "rlp_py", frozen, eq)]
+#[derive(PartialEq)]
+struct Block(RlpBlock);
+
+#[pymethods]
+impl Block {
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        let block = RlpBlock::from_bytes(data).map_err(error)?;
+        for tx in &block.transactions {
+            tx.fields().map_err(error)?;
+        }
+        Ok(Block(block))
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(bytes(py, rlp_rs::to_bytes(&self.0).map_err(error)?))
+    }
+
+    fn hash<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(bytes(py, self.0.hash().map_err(error)?))
+    }
+
+    #[getter]
+    fn header(&self) -> Header {
+        Header(self.0.header.clone())
+    }
+
+    #[getter]
+    fn transactions(&self) -> Vec<Transaction> {
+        self.0
+            .transactions
+            .iter()
+            .map(|tx| Transaction(tx.clone()))
+            .collect()
+    }
+
+    #[getter]
+    fn uncles(&self) -> Vec<Header> {
+        self.0.uncles.iter().cloned().map(Header).collect()
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        let hash = self.0.hash().map_err(error)?;
+        Ok(format!(
+            "Block(hash=0x{}, transactions={}, uncles={})",
+            hex(&hash),
+            self.0.transactions.len(),
+            self.0.uncles.len()
+        ))
+    }
+}
+
+#[pymodule]
+fn rlp_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("RlpError", module.py().get_type::<RlpError>())?;
+    module.add_function(wrap_pyfunction!(decode_rlp, module)?)?;
+    module.add_class::<Transaction>()?;
+    module.add_class::<Header>()?;
+    module.add_class::<Block>()?;
+    Ok(())
+}
@@ -0,0 +1,141 @@
+"""Tests of the Python bindings, run after `maturin develop` with `pytest tests`."""
+
+import pytest
+from rlp_py import Block, Header, RlpError, Transaction, decode_rlp
+
+LEGACY = bytes.fromhex(
+    "f85f800a82c35094095e7baea6a6c7c4c2dfeb977efac326af552d870a801ba09bea4c4daac7c7c52e093e6a4c35dbbcf8856f1af7b059ba20253e70848d094fa08a8fae537ce25ed8cb5af9adac3f141af69bd515bd2ba031522df09b97dd72b1"
+)
+DYNAMIC_FEE = bytes.fromhex(
+    "02f8a0018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8"
+)
+# the dynamic fee transaction with a 33 bytes storage key
+OVERSIZED_KEY = bytes.fromhex(
+    "02f8a2018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f83af838940000000000000000000000000000000000000001e2a101000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8"
+)
+LONDON_BLOCK = bytes.fromhex(
+    "f9030bf901fea083cafc574e1f51ba9dc0568fc617a08ea2429fb384059c972f13b19fa1c8dd55a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347948888f1f195afa192cfee860698584c030f4c9db1a0ef1552a40b7165c3cd773806b9e0c165b75356e0314bf0706f279c729f51e017a05fe50b260da6308036625b850b5d6ced6d0a9f814c0688bc91ffb7b7a3a54b67a0bc37d79753ad738a6dac4921e57392f145d8887476de3f783dfa7edae9283e52b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008302000001832fefd8825208845506eb0780a0bd4472abb6659ebe3ee06ee4d7b72a00a9f4d001caca51342001075469aff49888a13a5a8c8f2bb1c4843b9aca00f90106f85f800a82c35094095e7baea6a6c7c4c2dfeb977efac326af552d870a801ba09bea4c4daac7c7c52e093e6a4c35dbbcf8856f1af7b059ba20253e70848d094fa08a8fae537ce25ed8cb5af9adac3f141af69bd515bd2ba031522df09b97dd72b1b8a302f8a0018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8c0"
+)
+
+LEGACY_HASH = "77b19baa4de67e45a7b26e4a220bccdbb6731885aa9927064e239ca232023215"
+DYNAMIC_FEE_HASH = "c5a8f6026a3554e9731e6ad1c17a7450b8fe2d048cd755752cc985a89a2e125c"
+LONDON_BLOCK_HASH = "c7252048cd273fe0dac09650027d07f0e3da4ee0675ebbb26627cea92729c372"
+
+
+@pytest.mark.parametrize(
+    "data, expected",
+    [
+        ("80", b""),
+        ("00", b"\x00"),
+        ("83636174", b"cat"),
+        ("c0", []),
+        ("c88363617483646f67", [b"cat", b"dog"]),
+        ("c7c0c1c0c3c0c1c0", [[], [[]], [[], [[]]]]),
+    ],
+)
+def test_decode_rlp(data, expected):
+    assert decode_rlp(bytes.fromhex(data)) == expected
+
+
+@pytest.mark.parametrize("data", ["", "8100", "b80180", "c28200", "8080"])
+def test_decode_rlp_invalid(data):
+    with pytest.raises(RlpError):
+        decode_rlp(bytes.fromhex(data))
+
+
+def test_rlp_error_is_value_error():
+    with pytest.raises(ValueError, match="invalid bytes"):
+        decode_rlp(b"\x81\x00")
+
+
+def test_legacy_transaction():
+    tx = Transaction.from_bytes(LEGACY)
+    assert tx.tx_type == 0
+    assert tx.hash().hex() == LEGACY_HASH
+    assert tx.to_bytes() == LEGACY
+
+    assert tx.nonce == 0
+    assert tx.gas_price == 10
+    assert tx.gas_limit == 50000
+    assert tx.to == bytes.fromhex("095e7baea6a6c7c4c2dfeb977efac326af552d87")
+    assert tx.value == 10
+    assert tx.data == b""
+    assert tx.v == 27
+    assert tx.r == 0x9BEA4C4DAAC7C7C52E093E6A4C35DBBCF8856F1AF7B059BA20253E70848D094F
+    assert tx.chain_id is None
+    assert tx.max_fee_per_gas is None
+    assert tx.access_list is None
+    assert tx.y_parity is None
+
+
+def test_dynamic_fee_transaction():
+    tx = Transaction.from_bytes(DYNAMIC_FEE)
+    assert tx.tx_type == 2
+    assert tx.hash().hex() == DYNAMIC_FEE_HASH
+    assert tx.to_bytes() == DYNAMIC_FEE
+
+    assert tx.chain_id == 1
+    assert tx.max_priority_fee_per_gas == 0
+    assert tx.max_fee_per_gas == 1_000_000_000
+    assert tx.gas_limit == 0x1E241
+    assert tx.to == bytes.fromhex("095e7baea6a6c7c4c2dfeb977efac326af552d87")
+    assert tx.value == 0
+    assert tx.access_list == [(b"\x00" * 19 + b"\x01", [b"\x00" * 32])]
+    assert tx.y_parity == 0
+    assert tx.gas_price is None
+    assert tx.v is None
+    assert tx.blob_hashes is None
+
+    # as found in a block body
+    wrapped = bytes([0xB8, len(DYNAMIC_FEE)]) + DYNAMIC_FEE
+    assert Transaction.from_bytes(wrapped) == tx
+    assert Transaction.from_bytes(LEGACY) != tx
+    assert DYNAMIC_FEE_HASH in repr(tx)
+
+
+@pytest.mark.parametrize(
+    "data", [b"", LEGACY[:-1], DYNAMIC_FEE + b"\x00", b"\x05\xc0", OVERSIZED_KEY]
+)
+def test_transaction_invalid(data):
+    with pytest.raises(RlpError):
+        Transaction.from_bytes(data)
+
+
+def test_block():
+    block = Block.from_bytes(LONDON_BLOCK)
+    assert block.hash().hex() == LONDON_BLOCK_HASH
+    assert block.to_bytes() == LONDON_BLOCK
+    assert block.uncles == []
+
+    header = block.header
+    assert header.fork == "london"
+    assert header.hash() == block.hash()
+    assert header.coinbase == bytes.fromhex("8888f1f195afa192cfee860698584c030f4c9db1")
+    assert header.difficulty == 0x20000
+    assert header.number == 1
+    assert header.gas_limit == 0x2FEFD8
+    assert header.gas_used == 0x5208
+    assert header.time == 0x5506EB07
+    assert header.extra == b""
+    assert header.nonce == bytes.fromhex("a13a5a8c8f2bb1c4")
+    assert len(header.bloom) == 256
+    assert header.base_fee == 1_000_000_000
+    assert header.withdrawal_root is None
+    assert header.blob_gas_used is None
+
+    assert Header.from_bytes(header.to_bytes()) == header
+
+    transactions = block.transactions
+    assert [tx.hash().hex() for tx in transactions] == [LEGACY_HASH, DYNAMIC_FEE_HASH]
+    assert transactions[1] == Transaction.from_bytes(DYNAMIC_FEE)
+
+
+@pytest.mark.parametrize("data", [b"", LONDON_BLOCK[:-1], LONDON_BLOCK + b"\x80", b"\xc0"])
+def test_block_invalid(data):
+    with pytest.raises(RlpError):
+        Block.from_bytes(data)
+
+
+def test_header_invalid():
+    with pytest.raises(RlpError):
+        Header.from_bytes(bytes.fromhex("c3808080"))
@@ -0,0 +1,203 @@
+//! Integration with the `bytes` crate.
+//! Decoding from a `Bytes` buffer keeps the long payloads as slices of that buffer,
+//! and encoding writes directly into any `BufMut`.
+
+use crate::{
+    pack_rlp_into, read_item_header, ser::to_rlp, RecursiveBytes, Rlp, RlpError, Sink, SmallBytes,
+};
+use alloc::vec::Vec;
+use bytes::{BufMut, Bytes};
+use serde::{Deserialize, Serialize};
+
+/// Same as `unpack_rlp`, but the payloads that are not stored inline share `bytes` instead of being copied.
+pub fn unpack_rlp_shared(bytes: &Bytes) -> Result<Rlp, RlpError> {
+    if bytes.is_empty() {
+        return Err(RlpError::MissingBytes);
+    }
+    Ok(Rlp::new(
+        unpack_shared_element(bytes, 0, bytes.len())?.into(),
+    ))
+}
+
+/// Same as `from_bytes`, but decodes through `unpack_rlp_shared`.
+pub fn from_bytes_shared<'a, T>(bytes: &Bytes) -> Result<T, RlpError>
+where
+    T: Deserialize<'a>,
+{
+    let mut rlp = unpack_rlp_shared(bytes)?;
+    let t = T::deserialize(&mut rlp)?;
+    if !rlp.is_empty() {
+        return Err(RlpError::TrailingBytes);
+    }
+    Ok(t)
+}
+
+fn unpack_shared_element(
+    source: &Bytes,
+    mut cursor: usize,
+    end: usize,
+) -> Result<Vec<RecursiveBytes>, RlpError> {
+    let mut unpacked = Vec::new();
+
+    while cursor < end {
+        let header = read_item_header(&source[..end], cursor)?;
+        cursor = header.payload.end;
+
+        let ret = if header.kind.is_list() {
+            RecursiveBytes::Nested(unpack_shared_element(
+                source,
+                header.payload.start,
+                header.payload.end,
+            )?)
+        } else {
+            RecursiveBytes::Bytes(SmallBytes::from_shared(source.slice(header.payload)))
+        };
+
+        unpacked.push(ret);
+    }
+
+    Ok(unpacked)
+}
+
+struct BufMutSink<'a, B>(&'a mut B);
+
+impl<B: BufMut> Sink for BufMutSink<'_, B> {
+    fn put_u8(&mut self, byte: u8) {
+        self.0.put_u8(byte);
+    }
+
+    fn put_slice(&mut self, bytes: &[u8]) {
+        self.0.put_slice(bytes);
+    }
+}
+
+/// Same as `pack_rlp`, but writes into `buf`.
+pub fn pack_rlp_buf<B: BufMut>(rlp: &Rlp, buf: &mut B) -> Result<(), RlpError> {
+    pack_rlp_into(&mut BufMutSink(buf), rlp)
+}
+
+/// Same as `to_bytes`, but writes into `buf`.
+pub fn to_buf<T, B>(value: &T, buf: &mut B) -> Result<(), RlpError>
+where
+    T: Serialize,
+    B: BufMut,
+{
+    let rlp = to_rlp(value)?;
+    pack_rlp_buf(&rlp, buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_bytes_shared, pack_rlp_buf, to_buf, unpack_rlp_shared};
+    use crate::{pack_rlp, to_bytes, unpack_rlp, RecursiveBytes, RlpError};
+    use bytes::{Bytes, BytesMut};
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn unpack_shares_buffer() {
+        let mut encoded = vec![0xf8, 0x3e, 0x83, b'd', b'o', b'g', 0xb8, 0x38];
+        encoded.extend_from_slice(&[0x01; 56]);
+        let source = Bytes::from(encoded);
+
+        let rlp = unpack_rlp_shared(&source).unwrap();
+        let [RecursiveBytes::Bytes(dog), RecursiveBytes::Bytes(long)] = rlp.get_nested(0).unwrap()
+        else {
+            panic!("expected two strings");
+        };
+
+        assert_eq!(dog.as_slice(), b"dog");
+        assert_eq!(long.as_slice(), &[0x01; 56]);
+
+        // the short payload is inline, the long one is a slice of the source
+        let range = source.as_ptr_range();
+        assert!(dog.is_inline());
+        assert!(range.contains(&long.as_ptr()));
+
+        assert_eq!(rlp.get(0), unpack_rlp(&source).unwrap().get(0));
+        assert_eq!(rlp.get(0).unwrap().encoded_len(), source.len());
+
+        let mut buf = BytesMut::new();
+        pack_rlp_buf(&rlp, &mut buf).unwrap();
+        assert_eq!(buf.freeze(), source);
+
+        // mutating a shared payload copies it
+        let mut rlp = rlp;
+        let RecursiveBytes::Bytes(long) = rlp.get_path_mut(&[0, 1]).unwrap() else {
+            panic!("expected a string");
+        };
+        long[0] = 0x02;
+        assert!(!range.contains(&long.as_ptr()));
+        assert_eq!(source[8], 0x01);
+    }
+
+    #[test]
+    fn decode_shared() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Tx {
+            nonce: u64,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let tx = Tx {
+            nonce: 7,
+            data: vec![0xaa; 100],
+        };
+        let source = Bytes::from(to_bytes(&tx).unwrap());
+        assert_eq!(from_bytes_shared::<Tx>(&source).unwrap(), tx);
+
+        let mut trailing = source.to_vec();
+        trailing.push(0x80);
+        assert!(matches!(
+            from_bytes_shared::<Tx>(&Bytes::from(trailing)),
+            Err(RlpError::TrailingBytes)
+        ));
+    }
+
+    #[test]
+    fn unpack_shared_invalid() {
+        assert!(matches!(
+            unpack_rlp_shared(&Bytes::new()),
+            Err(RlpError::MissingBytes)
+        ));
+        assert!(matches!(
+            unpack_rlp_shared(&Bytes::from_static(&[0x81, 0x01])),
+            Err(RlpError::InvalidBytes)
+        ));
+        assert!(matches!(
+            unpack_rlp_shared(&Bytes::from_static(&[0xc2, 0x01])),
+            Err(RlpError::MissingBytes)
+        ));
+    }
+
+    #[test]
+    fn encode_into_buf_mut() {
+        #[derive(Serialize)]
+        struct Dog {
+            name: String,
+            #[serde(with = "serde_bytes")]
+            empty: Vec<u8>,
+            tricks: Vec<Vec<u8>>,
+            #[serde(with = "serde_bytes")]
+            blob: Vec<u8>,
+        }
+
+        let dog = Dog {
+            name: String::from("doggo"),
+            empty: vec![],
+            tricks: vec![vec![], vec![0, 1, 2, 255]],
+            blob: vec![7; 300],
+        };
+
+        let mut buf = BytesMut::new();
+        to_buf(&dog, &mut buf).unwrap();
+        assert_eq!(buf.as_ref(), to_bytes(&dog).unwrap());
+
+        // `Vec<u8>` is a `BufMut` too, and appends after what it already contains
+        let mut vec = vec![0xaa];
+        let rlp = unpack_rlp(&buf).unwrap();
+        pack_rlp_buf(&rlp, &mut vec).unwrap();
+        assert_eq!(vec[0], 0xaa);
+        assert_eq!(&vec[1..], pack_rlp(rlp).unwrap());
+    }
+}
@@ -0,0 +1,366 @@
+//! `tokio_util` codecs to read and write top-level RLP items over a byte stream.
+//! An item is recognized as complete from its prefix, so no extra length delimiter is needed.
+
+use crate::{
+    buf::{pack_rlp_buf, unpack_rlp_shared},
+    read_item_header, read_item_prefix,
+    ser::to_rlp,
+    Rlp, RlpError,
+};
+use bytes::BytesMut;
+use core::{fmt, marker::PhantomData};
+use serde::{de::DeserializeOwned, Serialize};
+use std::io;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Default maximum size of a frame, prefix included.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+#[derive(Debug)]
+pub enum CodecError {
+    Io(io::Error),
+    Rlp(RlpError),
+    /// The frame is larger than the maximum frame length of the codec.
+    FrameTooLarge,
+}
+
+impl From<io::Error> for CodecError {
+    fn from(value: io::Error) -> Self {
+        CodecError::Io(value)
+    }
+}
+
+impl From<RlpError> for CodecError {
+    fn from(value: RlpError) -> Self {
+        CodecError::Rlp(value)
+    }
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodecError::Io(err) => err.fmt(formatter),
+            CodecError::Rlp(err) => err.fmt(formatter),
+            CodecError::FrameTooLarge => formatter.write_str("frame too large"),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+/// Frames a stream into top-level RLP items, decoded as `Rlp`.
+#[derive(Debug, Clone)]
+pub struct RlpCodec {
+    max_frame_len: usize,
+}
+
+impl Default for RlpCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RlpCodec {
+    pub fn new() -> Self {
+        Self::with_max_frame_len(DEFAULT_MAX_FRAME_LEN)
+    }
+
+    pub fn with_max_frame_len(max_frame_len: usize) -> Self {
+        Self { max_frame_len }
+    }
+
+    pub fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// Split the next complete item off `src`, if any.
+    fn decode_frame(&self, src: &mut BytesMut) -> Result<Option<BytesMut>, CodecError> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+
+        // the prefix is enough to reject a frame that is too large, before its payload arrives
+        let end = match read_item_prefix(src, 0) {
+            Ok(header) => header.payload.end,
+            Err(RlpError::MissingBytes) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        if end > self.max_frame_len {
+            return Err(CodecError::FrameTooLarge);
+        }
+        if end > src.len() {
+            src.reserve(end - src.len());
+            return Ok(None);
+        }
+
+        read_item_header(src, 0)?;
+        Ok(Some(src.split_to(end)))
+    }
+
+    fn encode_frame(&self, rlp: &Rlp, dst: &mut BytesMut) -> Result<(), CodecError> {
+        let len = rlp.0.iter().map(|rec| rec.encoded_len()).sum();
+        if len > self.max_frame_len {
+            return Err(CodecError::FrameTooLarge);
+        }
+
+        dst.reserve(len);
+        pack_rlp_buf(rlp, dst)?;
+        Ok(())
+    }
+}
+
+impl Decoder for RlpCodec {
+    type Item = Rlp;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode_frame(src)? {
+            // the payloads of the item are slices of the frame
+            Some(frame) => Ok(Some(unpack_rlp_shared(&frame.freeze())?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Encoder<Rlp> for RlpCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: Rlp, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode_frame(&item, dst)
+    }
+}
+
+impl Encoder<&Rlp> for RlpCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: &Rlp, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode_frame(item, dst)
+    }
+}
+
+/// Frames a stream into top-level RLP items, each deserialized as a `T`.
+pub struct TypedRlpCodec<T> {
+    inner: RlpCodec,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> fmt::Debug for TypedRlpCodec<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("TypedRlpCodec")
+            .field("max_frame_len", &self.inner.max_frame_len)
+            .finish()
+    }
+}
+
+impl<T> Clone for TypedRlpCodec<T> {
+    fn clone(&self) -> Self {
+        Self::with_max_frame_len(self.inner.max_frame_len)
+    }
+}
+
+impl<T> Default for TypedRlpCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> TypedRlpCodec<T> {
+    pub fn new() -> Self {
+        Self::with_max_frame_len(DEFAULT_MAX_FRAME_LEN)
+    }
+
+    pub fn with_max_frame_len(max_frame_len: usize) -> Self {
+        Self {
+            inner: RlpCodec::with_max_frame_len(max_frame_len),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn max_frame_len(&self) -> usize {
+        self.inner.max_frame_len
+    }
+}
+
+impl<T> Decoder for TypedRlpCodec<T>
+where
+    T: DeserializeOwned,
+{
+    type Item = T;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.inner.decode_frame(src)? {
+            Some(frame) => Ok(Some(crate::from_bytes(&frame)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T> Encoder<T> for TypedRlpCodec<T>
+where
+    T: Serialize,
+{
+    type Error = CodecError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let rlp = to_rlp(&item)?;
+        self.inner.encode_frame(&rlp, dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CodecError, RlpCodec, TypedRlpCodec};
+    use crate::{pack_rlp, to_bytes, RecursiveBytes, RlpError};
+    use bytes::BytesMut;
+    use futures::{SinkExt, StreamExt};
+    use serde::{Deserialize, Serialize};
+    use tokio::io::AsyncWriteExt;
+    use tokio_util::codec::{Decoder, FramedRead, FramedWrite};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Ping {
+        id: u64,
+        #[serde(with = "serde_bytes")]
+        payload: Vec<u8>,
+    }
+
+    #[tokio::test]
+    async fn duplex_typed() {
+        let (client, server) = tokio::io::duplex(16);
+        let pings: Vec<_> = (0..20)
+            .map(|id| Ping {
+                id,
+                payload: vec![id as u8; id as usize * 10],
+            })
+            .collect();
+
+        let writer = async {
+            let mut sink = FramedWrite::new(client, TypedRlpCodec::<Ping>::new());
+            for ping in pings.iter() {
+                sink.send(Ping {
+                    id: ping.id,
+                    payload: ping.payload.clone(),
+                })
+                .await
+                .unwrap();
+            }
+        };
+        let reader = async {
+            let stream = FramedRead::new(server, TypedRlpCodec::<Ping>::new());
+            stream.map(Result::unwrap).collect::<Vec<_>>().await
+        };
+
+        let ((), received) = tokio::join!(writer, reader);
+        assert_eq!(received, pings);
+    }
+
+    #[tokio::test]
+    async fn duplex_byte_by_byte() {
+        let (mut client, server) = tokio::io::duplex(1);
+        let mut bytes = to_bytes(&vec![vec![0u8; 100], vec![1; 2]]).unwrap();
+        bytes.extend_from_slice(&[0x0f, 0x80, 0xc0]);
+
+        let writer = async {
+            for byte in bytes.iter() {
+                client.write_all(&[*byte]).await.unwrap();
+            }
+            drop(client);
+        };
+        let reader = async {
+            let stream = FramedRead::new(server, RlpCodec::new());
+            stream.map(Result::unwrap).collect::<Vec<_>>().await
+        };
+
+        let ((), received) = tokio::join!(writer, reader);
+        assert_eq!(received.len(), 4);
+        let packed: Vec<u8> = received
+            .into_iter()
+            .flat_map(|rlp| pack_rlp(rlp).unwrap())
+            .collect();
+        assert_eq!(packed, bytes);
+    }
+
+    #[tokio::test]
+    async fn duplex_truncated() {
+        let (mut client, server) = tokio::io::duplex(64);
+        client.write_all(&[0x83, b'd', b'o']).await.unwrap();
+        drop(client);
+
+        let mut stream = FramedRead::new(server, RlpCodec::new());
+        assert!(matches!(stream.next().await, Some(Err(CodecError::Io(_)))));
+    }
+
+    #[test]
+    fn max_frame_len() {
+        let mut codec = RlpCodec::with_max_frame_len(4);
+
+        let src = &mut BytesMut::from(&[0x83, b'd', b'o', b'g'][..]);
+        assert!(codec.decode(src).unwrap().is_some());
+        assert!(src.is_empty());
+
+        // the declared length is checked before the payload arrives
+        let src = &mut BytesMut::from(&[0x84][..]);
+        assert!(matches!(codec.decode(src), Err(CodecError::FrameTooLarge)));
+
+        let src = &mut BytesMut::from(&[0xb8][..]);
+        assert!(codec.decode(src).unwrap().is_none());
+        src.extend_from_slice(&[0x38]);
+        assert!(matches!(codec.decode(src), Err(CodecError::FrameTooLarge)));
+
+        let src = &mut BytesMut::from(&[0xf9, 0xff, 0xff][..]);
+        assert!(matches!(codec.decode(src), Err(CodecError::FrameTooLarge)));
+
+        let src = &mut BytesMut::from(&[0x83, b'd'][..]);
+        assert!(codec.decode(src).unwrap().is_none());
+
+        let mut typed = TypedRlpCodec::<Ping>::with_max_frame_len(4);
+        let mut dst = BytesMut::new();
+        let ping = Ping {
+            id: 1,
+            payload: vec![2; 4],
+        };
+        assert!(matches!(
+            tokio_util::codec::Encoder::encode(&mut typed, ping, &mut dst),
+            Err(CodecError::FrameTooLarge)
+        ));
+        assert!(dst.is_empty());
+    }
+
+    #[test]
+    fn shared_frame() {
+        let mut codec = RlpCodec::new();
+        let bytes = to_bytes(&Ping {
+            id: 1,
+            payload: vec![2; 40],
+        })
+        .unwrap();
+        let src = &mut BytesMut::from(&bytes[..]);
+        let range = src.as_ptr_range();
+
+        let rlp = codec.decode(src).unwrap().unwrap();
+        let [RecursiveBytes::Bytes(id), RecursiveBytes::Bytes(payload)] =
+            rlp.get_nested(0).unwrap()
+        else {
+            panic!("expected two strings");
+        };
+        assert!(id.is_inline());
+        assert!(range.contains(&payload.as_ptr()));
+        assert_eq!(pack_rlp(rlp).unwrap(), bytes);
+    }
+
+    #[test]
+    fn invalid_frame() {
+        let mut codec = RlpCodec::new();
+        let src = &mut BytesMut::from(&[0x81, 0x01][..]);
+        assert!(matches!(
+            codec.decode(src),
+            Err(CodecError::Rlp(RlpError::InvalidBytes))
+        ));
+
+        let mut typed = TypedRlpCodec::<Ping>::new();
+        let src = &mut BytesMut::from(&to_bytes(&vec!["a"]).unwrap()[..]);
+        assert!(matches!(typed.decode(src), Err(CodecError::Rlp(_))));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{unpack_rlp, RecursiveBytes, Rlp, RlpError};
+use crate::layout::{AS_LIST, INLINE};
+use crate::{unpack_rlp, RecursiveBytes, Rlp, RlpError, SmallBytes};
+use alloc::string::String;
+use alloc::vec::Vec;
 use paste::paste;
 use serde::de::{EnumAccess, SeqAccess, VariantAccess};
 use serde::{Deserialize, Deserializer};
@@ -7,7 +10,23 @@ macro_rules! parse_int {
     ($ty:ty) => {
         paste! {
             fn [<parse_ $ty>](&mut self) -> Result<[<$ty>], RlpError> {
-                let bytes = self.need_bytes_len::<{std::mem::size_of::<$ty>()}>(true)?;
+                let bytes = self.need_bytes_len::<{core::mem::size_of::<$ty>()}>(true)?;
+                Ok([<$ty>]::from_be_bytes(bytes))
+            }
+        }
+    };
+}
+
+/// Same as `parse_int`, but also rejects the values that would be negative,
+/// since negative values are never encoded by the default signed integer policy.
+macro_rules! parse_signed {
+    ($ty:ty) => {
+        paste! {
+            fn [<parse_ $ty>](&mut self) -> Result<[<$ty>], RlpError> {
+                let bytes = self.need_bytes_len::<{core::mem::size_of::<$ty>()}>(true)?;
+                if bytes[0] & 0x80 != 0 {
+                    return Err(RlpError::InvalidBytes);
+                }
                 Ok([<$ty>]::from_be_bytes(bytes))
             }
         }
@@ -42,7 +61,7 @@ impl Rlp {
         Ok(bytes.as_slice())
     }
 
-    fn need_bytes(&mut self) -> Result<Vec<u8>, RlpError> {
+    fn need_bytes(&mut self) -> Result<SmallBytes, RlpError> {
         let RecursiveBytes::Bytes(bytes) = self.0.pop_front().ok_or(RlpError::MissingBytes)? else {
             return Err(RlpError::ExpectedBytes);
         };
@@ -64,7 +83,7 @@ impl Rlp {
         &mut self,
         check_trailing: bool,
     ) -> Result<[u8; S], RlpError> {
-        let mut bytes = self.need_bytes()?;
+        let bytes = self.need_bytes()?;
         if bytes.len() > S {
             return Err(RlpError::InvalidLength);
         }
@@ -73,15 +92,9 @@ impl Rlp {
             return Err(RlpError::TrailingBytes);
         }
 
-        bytes = if S > bytes.len() {
-            let mut new_bytes = vec![0; S - bytes.len()];
-            new_bytes.append(&mut bytes);
-            new_bytes
-        } else {
-            bytes
-        };
-
-        Ok(bytes.try_into().unwrap())
+        let mut padded = [0; S];
+        padded[(S - bytes.len())..].copy_from_slice(&bytes);
+        Ok(padded)
     }
 
     fn parse_bool(&mut self) -> Result<bool, RlpError> {
@@ -95,55 +108,70 @@ impl Rlp {
         Ok(bool_val)
     }
 
-    parse_int!(i8);
-    parse_int!(i16);
-    parse_int!(i32);
-    parse_int!(i64);
+    parse_signed!(i8);
+    parse_signed!(i16);
+    parse_signed!(i32);
+    parse_signed!(i64);
+    parse_signed!(i128);
 
     parse_int!(u8);
     parse_int!(u16);
     parse_int!(u32);
     parse_int!(u64);
+    parse_int!(u128);
 
+    /// chars are encoded as the integer of their code point
     fn parse_char(&mut self) -> Result<char, RlpError> {
-        let bytes = self.need_bytes_len::<1>(false)?;
-        let byte = bytes[0];
-        Ok(byte.into())
+        let bytes = self.need_bytes_len::<4>(true)?;
+        char::from_u32(u32::from_be_bytes(bytes)).ok_or(RlpError::InvalidBytes)
     }
 
     fn parse_string(&mut self) -> Result<String, RlpError> {
         let bytes = self.need_bytes()?;
-        String::from_utf8(bytes).map_err(|_| RlpError::InvalidBytes)
+        String::from_utf8(bytes.into_vec()).map_err(|_| RlpError::InvalidBytes)
     }
 
-    fn parse_bytes(&mut self) -> Result<Vec<u8>, RlpError> {
+    fn parse_bytes(&mut self) -> Result<SmallBytes, RlpError> {
         self.need_bytes()
     }
 }
 
-struct Seq {
-    de: Vec<Rlp>,
+/// Reads the elements of a sequence from the items of `de`, each element taking as many items as it needs.
+/// A sequence with a known length stops after `len` elements, otherwise it stops when `de` is empty.
+struct Seq<'a> {
+    de: &'a mut Rlp,
+    len: Option<usize>,
 }
 
-impl Seq {
-    // NOTE elements should be in reverse order
-    fn new(de: Vec<Rlp>) -> Self {
-        Seq { de }
+impl<'a> Seq<'a> {
+    fn new(de: &'a mut Rlp, len: Option<usize>) -> Self {
+        Seq { de, len }
     }
 }
 
-impl<'de> SeqAccess<'de> for &mut Seq {
+impl<'de, 'a> SeqAccess<'de> for Seq<'a> {
     type Error = RlpError;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
     where
         T: serde::de::DeserializeSeed<'de>,
     {
-        if self.de.is_empty() {
-            Ok(None)
-        } else {
-            let rlp = &mut self.de.pop().unwrap();
-            seed.deserialize(rlp).map(Some)
+        match &mut self.len {
+            Some(0) => Ok(None),
+            Some(len) => {
+                *len -= 1;
+                seed.deserialize(&mut *self.de).map(Some)
+            }
+            None if self.de.is_empty() => Ok(None),
+            None => {
+                let items = self.de.len();
+                let value = seed.deserialize(&mut *self.de)?;
+                // an element taking no item would be read again and again
+                if self.de.len() == items {
+                    return Err(RlpError::InvalidLength);
+                }
+                Ok(Some(value))
+            }
         }
     }
 }
@@ -182,9 +210,7 @@ impl<'de, 'a> VariantAccess<'de> for Enum<'a> {
     where
         T: serde::de::DeserializeSeed<'de>,
     {
-        let bytes = self.de.parse_bytes()?;
-        let rlp = &mut Rlp(vec![RecursiveBytes::Bytes(bytes)].into());
-        seed.deserialize(rlp)
+        seed.deserialize(&mut *self.de)
     }
 
     fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
@@ -206,14 +232,27 @@ impl<'de, 'a> VariantAccess<'de> for Enum<'a> {
     }
 }
 
-impl<'de, 'a> Deserializer<'de> for &'a mut Rlp {
+impl<'de> Deserializer<'de> for &mut Rlp {
     type Error = RlpError;
 
-    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    /// RLP is self-describing as far as strings and lists go:
+    /// strings are visited as bytes and lists as sequences.
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
-        unimplemented!();
+        match self.need_next()? {
+            RecursiveBytes::Bytes(bytes) => visitor.visit_bytes(&bytes),
+            RecursiveBytes::EmptyList => visitor.visit_bytes(&[]),
+            RecursiveBytes::Nested(recs) => {
+                let rlp = &mut Rlp::new(recs.into());
+                let res = visitor.visit_seq(Seq::new(rlp, None))?;
+                match rlp.is_empty() {
+                    true => Ok(res),
+                    false => Err(RlpError::InvalidLength),
+                }
+            }
+        }
     }
 
     fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -252,6 +291,13 @@ impl<'de, 'a> Deserializer<'de> for &'a mut Rlp {
         visitor.visit_i64(self.parse_i64()?)
     }
 
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_i128(self.parse_i128()?)
+    }
+
     fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
@@ -280,6 +326,13 @@ impl<'de, 'a> Deserializer<'de> for &'a mut Rlp {
         visitor.visit_u64(self.parse_u64()?)
     }
 
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_u128(self.parse_u128()?)
+    }
+
     fn deserialize_f32<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
@@ -328,7 +381,7 @@ impl<'de, 'a> Deserializer<'de> for &'a mut Rlp {
     where
         V: serde::de::Visitor<'de>,
     {
-        visitor.visit_byte_buf(self.parse_bytes()?)
+        visitor.visit_byte_buf(self.parse_bytes()?.into_vec())
     }
 
     fn deserialize_option<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
@@ -359,28 +412,33 @@ impl<'de, 'a> Deserializer<'de> for &'a mut Rlp {
 
     fn deserialize_newtype_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
-        visitor.visit_newtype_struct(self)
+        match name {
+            AS_LIST => {
+                let rlp = &mut Rlp::new(self.need_nested()?.into());
+                let res = visitor.visit_newtype_struct(&mut *rlp)?;
+                match rlp.is_empty() {
+                    true => Ok(res),
+                    false => Err(RlpError::InvalidLength),
+                }
+            }
+            INLINE => visitor.visit_newtype_struct(Inlined(self)),
+            _ => visitor.visit_newtype_struct(self),
+        }
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
-        let recs = self.need_nested()?;
-        let rlps: Vec<Rlp> = recs
-            .into_iter()
-            .rev()
-            .map(|rec| Rlp(vec![rec].into()))
-            .collect();
-        let mut seq = Seq::new(rlps);
-        let res = visitor.visit_seq(&mut seq)?;
-        match seq.de.is_empty() {
+        let rlp = &mut Rlp::new(self.need_nested()?.into());
+        let res = visitor.visit_seq(Seq::new(rlp, None))?;
+        match rlp.is_empty() {
             true => Ok(res),
             false => Err(RlpError::InvalidLength),
         }
@@ -390,16 +448,8 @@ impl<'de, 'a> Deserializer<'de> for &'a mut Rlp {
     where
         V: serde::de::Visitor<'de>,
     {
-        let rlps = (0..len)
-            .map(|_| self.need_next().map(|rec| Rlp(vec![rec].into())))
-            .collect::<Result<Vec<_>, _>>()?;
-        let rlps = rlps.into_iter().rev().collect();
-        let mut seq = Seq::new(rlps);
-        let res = visitor.visit_seq(&mut seq)?;
-        match seq.de.is_empty() {
-            true => Ok(res),
-            false => Err(RlpError::InvalidLength),
-        }
+        // tuples are inlined in the enclosing list
+        visitor.visit_seq(Seq::new(self, Some(len)))
     }
 
     fn deserialize_tuple_struct<V>(
@@ -456,18 +506,145 @@ impl<'de, 'a> Deserializer<'de> for &'a mut Rlp {
             }
             RecursiveBytes::Nested(recs) => {
                 // flatten structure
-                *self = Rlp::new(recs.into());
+                for rec in recs.into_iter().rev() {
+                    self.0.push_front(rec);
+                }
                 self.deserialize_str(visitor)
             }
             RecursiveBytes::EmptyList => Err(RlpError::InvalidBytes),
         }
     }
 
-    fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'de>,
     {
-        unimplemented!()
+        self.need_next()?;
+        visitor.visit_unit()
+    }
+}
+
+macro_rules! forward_inlined {
+    ($($method:ident),*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: serde::de::Visitor<'de>,
+            {
+                self.0.$method(visitor)
+            }
+        )*
+    };
+}
+
+/// Deserializes a value whose outermost list is inlined in the enclosing one,
+/// so its elements are read directly from the enclosing list.
+struct Inlined<'a>(&'a mut Rlp);
+
+impl<'de, 'a> Deserializer<'de> for Inlined<'a> {
+    type Error = RlpError;
+
+    forward_inlined!(
+        deserialize_any,
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_i128,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_u128,
+        deserialize_f32,
+        deserialize_f64,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_option,
+        deserialize_unit,
+        deserialize_map,
+        deserialize_identifier,
+        deserialize_ignored_any
+    );
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.0.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match name {
+            // the list of `AsList` is the one being inlined
+            AS_LIST => visitor.visit_newtype_struct(self.0),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_seq(Seq::new(self.0, None))
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.0.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_seq(Seq::new(self.0, Some(len)))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_seq(Seq::new(self.0, Some(fields.len())))
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.0.deserialize_enum(name, variants, visitor)
     }
 }
 
@@ -481,17 +658,15 @@ mod tests {
 
     #[test]
     fn de_i8() {
-        let rlp = &mut Rlp::new_unary(RecursiveBytes::Bytes(vec![255]));
-        let num: i8 = from_rlp(rlp).unwrap();
-        assert_eq!(num, -1);
+        let rlp = &mut Rlp::new_unary(RecursiveBytes::Bytes(vec![255].into()));
+        assert!(matches!(from_rlp::<i8>(rlp), Err(RlpError::InvalidBytes)));
 
-        let rlp = &mut Rlp::new_unary(RecursiveBytes::Bytes(vec![127]));
+        let rlp = &mut Rlp::new_unary(RecursiveBytes::Bytes(vec![127].into()));
         let num: i8 = from_rlp(rlp).unwrap();
         assert_eq!(num, 127);
 
-        let rlp = &mut Rlp::new_unary(RecursiveBytes::Bytes(vec![128]));
-        let num: i8 = from_rlp(rlp).unwrap();
-        assert_eq!(num, -128);
+        let rlp = &mut Rlp::new_unary(RecursiveBytes::Bytes(vec![128].into()));
+        assert!(matches!(from_rlp::<i8>(rlp), Err(RlpError::InvalidBytes)));
 
         let num: i8 = from_bytes(&[127]).unwrap();
         assert_eq!(num, 127);
@@ -499,13 +674,43 @@ mod tests {
         let num: i8 = from_bytes(&[0x80]).unwrap();
         assert_eq!(num, 0);
 
-        let num: i8 = from_bytes(&[0x81, 255]).unwrap();
-        assert_eq!(num, -1);
+        assert!(matches!(
+            from_bytes::<i8>(&[0x81, 255]),
+            Err(RlpError::InvalidBytes)
+        ));
+    }
+
+    #[test]
+    fn de_signed() {
+        let num: i64 = from_bytes(&[0x82, 0x04, 0x00]).unwrap();
+        assert_eq!(num, 1024);
+
+        // 0x80 is the sign bit of an i16, but not of an i32
+        assert!(matches!(
+            from_bytes::<i16>(&[0x82, 0x80, 0x00]),
+            Err(RlpError::InvalidBytes)
+        ));
+        assert_eq!(from_bytes::<i32>(&[0x82, 0x80, 0x00]).unwrap(), 0x8000);
+
+        assert!(matches!(
+            from_bytes::<i32>(&[0x82, 0x00, 0x01]),
+            Err(RlpError::TrailingBytes)
+        ));
+
+        let mut bytes = vec![0x80 + 16];
+        bytes.extend_from_slice(&i128::MAX.to_be_bytes());
+        assert_eq!(from_bytes::<i128>(&bytes).unwrap(), i128::MAX);
+        bytes[1] = 0xff;
+        assert!(matches!(
+            from_bytes::<i128>(&bytes),
+            Err(RlpError::InvalidBytes)
+        ));
+        assert_eq!(from_bytes::<u128>(&bytes).unwrap(), u128::MAX);
     }
 
     #[test]
     fn de_u32() {
-        let rlp = &mut Rlp::new_unary(RecursiveBytes::Bytes(vec![255, 255, 255, 255]));
+        let rlp = &mut Rlp::new_unary(RecursiveBytes::Bytes(vec![255, 255, 255, 255].into()));
         let num: u32 = from_rlp(rlp).unwrap();
         assert_eq!(num, u32::MAX);
 
@@ -523,8 +728,8 @@ mod tests {
         let rlp = &mut Rlp::new(
             vec![RecursiveBytes::Nested(
                 [
-                    RecursiveBytes::Bytes(vec![0]),
-                    RecursiveBytes::Bytes(vec![1]),
+                    RecursiveBytes::Bytes(vec![0].into()),
+                    RecursiveBytes::Bytes(vec![1].into()),
                 ]
                 .into(),
             )]
@@ -538,8 +743,8 @@ mod tests {
     fn de_tuple_bool() {
         let rlp = &mut Rlp::new(
             vec![
-                RecursiveBytes::Bytes(vec![0]),
-                RecursiveBytes::Bytes(vec![1]),
+                RecursiveBytes::Bytes(vec![0].into()),
+                RecursiveBytes::Bytes(vec![1].into()),
             ]
             .into(),
         );
@@ -584,8 +789,8 @@ mod tests {
         assert_eq!(
             unpack_rlp(&bytes).unwrap().0,
             vec![RecursiveBytes::Nested(vec![
-                RecursiveBytes::Bytes(cat.as_bytes().to_vec()),
-                RecursiveBytes::Bytes(dog.as_bytes().to_vec())
+                RecursiveBytes::Bytes(cat.as_bytes().to_vec().into()),
+                RecursiveBytes::Bytes(dog.as_bytes().to_vec().into())
             ])]
         );
 
@@ -611,7 +816,7 @@ mod tests {
         let bytes = [0xc1, 0x80];
 
         let deserialized = from_bytes::<HasVec>(&bytes).unwrap();
-        assert_eq!(deserialized.vec, vec![]);
+        assert_eq!(deserialized.vec, Vec::<u8>::new());
 
         let serialized = to_bytes(&deserialized).unwrap();
         assert_eq!(serialized, &bytes);
@@ -662,9 +867,9 @@ mod tests {
     #[derive(Debug, PartialEq, Deserialize)]
     enum Message {
         Quit,
-        Move { x: i32, y: i32 },
+        Move { x: u32, y: u32 },
         Write(String),
-        ChangeColor(i32, i32, i32),
+        ChangeColor(u32, u32, u32),
     }
 
     #[test]
@@ -690,64 +895,138 @@ mod tests {
 
     #[test]
     fn de_enum_struct() {
-        // ["Move", [-1, -1]]
+        // ["Move", [u32::MAX, u32::MAX]]
         let mut message = Vec::new();
-        message.push(0xc0 + "Move".len() as u8 + (i32::BITS / 8 * 2) as u8 + 4);
+        message.push(0xc0 + "Move".len() as u8 + (u32::BITS / 8 * 2) as u8 + 4);
         message.push(0x80 + "Move".len() as u8);
         message.extend_from_slice("Move".as_bytes());
-        message.push(0xc0 + (i32::BITS / 8 * 2) as u8 + 2);
-        message.push(0x80 + (i32::BITS / 8) as u8);
-        message.extend_from_slice(&(-1i32).to_be_bytes());
-        message.push(0x80 + (i32::BITS / 8) as u8);
-        message.extend_from_slice(&(-1i32).to_be_bytes());
+        message.push(0xc0 + (u32::BITS / 8 * 2) as u8 + 2);
+        message.push(0x80 + (u32::BITS / 8) as u8);
+        message.extend_from_slice(&u32::MAX.to_be_bytes());
+        message.push(0x80 + (u32::BITS / 8) as u8);
+        message.extend_from_slice(&u32::MAX.to_be_bytes());
 
         assert_eq!(
             unpack_rlp(&message).unwrap().0,
             [RecursiveBytes::Nested(vec![
-                RecursiveBytes::Bytes("Move".as_bytes().to_vec()),
+                RecursiveBytes::Bytes("Move".as_bytes().to_vec().into()),
                 RecursiveBytes::Nested(vec![
-                    RecursiveBytes::Bytes((-1i32).to_be_bytes().to_vec()),
-                    RecursiveBytes::Bytes((-1i32).to_be_bytes().to_vec())
+                    RecursiveBytes::Bytes(u32::MAX.to_be_bytes().to_vec().into()),
+                    RecursiveBytes::Bytes(u32::MAX.to_be_bytes().to_vec().into())
                 ])
             ])]
         );
 
         assert_eq!(
             from_bytes::<Message>(&message).unwrap(),
-            Message::Move { x: -1, y: -1 }
+            Message::Move {
+                x: u32::MAX,
+                y: u32::MAX
+            }
         );
     }
 
     #[test]
     fn de_enum_tuple() {
         let mut message = Vec::new();
-        message.push(0xc0 + "ChangeColor".len() as u8 + ((i32::BITS / 8 + 1) * 3) as u8 + 2);
+        message.push(0xc0 + "ChangeColor".len() as u8 + ((u32::BITS / 8 + 1) * 3) as u8 + 2);
         message.push(0x80 + "ChangeColor".len() as u8);
         message.extend_from_slice("ChangeColor".as_bytes());
-        message.push(0xc0 + ((i32::BITS / 8 + 1) * 3) as u8);
-        message.push(0x80 + (i32::BITS / 8) as u8);
-        message.extend_from_slice(&(-1i32).to_be_bytes());
-        message.push(0x80 + (i32::BITS / 8) as u8);
-        message.extend_from_slice(&(-212412i32).to_be_bytes());
-        message.push(0x80 + (i32::BITS / 8) as u8);
-        message.extend_from_slice(&(2147483647i32).to_be_bytes());
+        message.push(0xc0 + ((u32::BITS / 8 + 1) * 3) as u8);
+        message.push(0x80 + (u32::BITS / 8) as u8);
+        message.extend_from_slice(&u32::MAX.to_be_bytes());
+        message.push(0x80 + (u32::BITS / 8) as u8);
+        message.extend_from_slice(&0xdeadbeefu32.to_be_bytes());
+        message.push(0x80 + (u32::BITS / 8) as u8);
+        message.extend_from_slice(&2147483647u32.to_be_bytes());
 
         let rlp = unpack_rlp(&message).unwrap();
         assert_eq!(
             rlp.0,
             [RecursiveBytes::Nested(vec![
-                RecursiveBytes::Bytes("ChangeColor".as_bytes().to_vec()),
+                RecursiveBytes::Bytes("ChangeColor".as_bytes().to_vec().into()),
                 RecursiveBytes::Nested(vec![
-                    RecursiveBytes::Bytes((-1i32).to_be_bytes().to_vec()),
-                    RecursiveBytes::Bytes((-212412i32).to_be_bytes().to_vec()),
-                    RecursiveBytes::Bytes((2147483647i32).to_be_bytes().to_vec())
+                    RecursiveBytes::Bytes(u32::MAX.to_be_bytes().to_vec().into()),
+                    RecursiveBytes::Bytes(0xdeadbeefu32.to_be_bytes().to_vec().into()),
+                    RecursiveBytes::Bytes(2147483647u32.to_be_bytes().to_vec().into())
                 ])
             ])]
         );
 
         assert_eq!(
             from_bytes::<Message>(&message).unwrap(),
-            Message::ChangeColor(-1, -212412, 2147483647)
+            Message::ChangeColor(u32::MAX, 0xdeadbeef, 2147483647)
+        );
+    }
+
+    #[test]
+    fn de_enum_round_trip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        enum Shape {
+            Point,
+            Circle(u32),
+            Polygon(Vec<u16>),
+            Segment(u8, u8),
+            Rect { width: u32, height: u32 },
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Drawing {
+            shapes: Vec<Shape>,
+            last: Shape,
+            layer: u8,
+        }
+
+        let drawing = Drawing {
+            shapes: vec![
+                Shape::Point,
+                Shape::Circle(1),
+                Shape::Polygon(vec![1, 0x100]),
+                Shape::Segment(3, 4),
+                Shape::Rect {
+                    width: 5,
+                    height: 6,
+                },
+                Shape::Point,
+            ],
+            last: Shape::Rect {
+                width: 0,
+                height: u32::MAX,
+            },
+            layer: 7,
+        };
+
+        let bytes = to_bytes(&drawing).unwrap();
+        assert_eq!(from_bytes::<Drawing>(&bytes).unwrap(), drawing);
+    }
+
+    #[test]
+    fn de_char() {
+        let tests = [
+            ('\0', vec![0x80]),
+            ('A', vec![0x41]),
+            ('é', vec![0x81, 0xe9]),
+            ('€', vec![0x82, 0x20, 0xac]),
+            ('\u{10ffff}', vec![0x83, 0x10, 0xff, 0xff]),
+        ];
+
+        for (i, (ch, bytes)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            assert_eq!(to_bytes(&ch).unwrap(), bytes);
+            assert_eq!(from_bytes::<char>(&bytes).unwrap(), ch);
+
+            println!("ok");
+        }
+
+        // surrogates are not chars
+        assert_eq!(
+            from_bytes::<char>(&[0x82, 0xd8, 0x00]).unwrap_err(),
+            RlpError::InvalidBytes
+        );
+        assert_eq!(
+            from_bytes::<char>(&[0x83, 0x11, 0x00, 0x00]).unwrap_err(),
+            RlpError::InvalidBytes
         );
     }
 
@@ -798,4 +1077,48 @@ mod tests {
 
         assert!(crate::unpack_rlp(&bytes).is_err());
     }
+
+    #[test]
+    fn de_any_transcode() {
+        // [["cat", [1, 1024]], "", []]
+        let bytes = [
+            0xcc, 0xc9, 0x83, b'c', b'a', b't', 0xc4, 0x01, 0x82, 0x04, 0x00, 0x80, 0xc0,
+        ];
+        let rlp = &mut unpack_rlp(&bytes).unwrap();
+
+        let mut json = Vec::new();
+        serde_transcode::transcode(&mut *rlp, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        assert!(rlp.is_empty());
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            "[[[99,97,116],[[1],[4,0]]],[],[]]"
+        );
+    }
+
+    #[test]
+    fn de_any_untagged() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(untagged)]
+        enum Item {
+            // first, since `ByteBuf` also accepts lists of bytes
+            List(Vec<Item>),
+            Bytes(serde_bytes::ByteBuf),
+        }
+
+        let item: Item = from_bytes(&[0xc5, 0x83, b'c', b'a', b't', 0xc0]).unwrap();
+        assert_eq!(
+            item,
+            Item::List(vec![
+                Item::Bytes(serde_bytes::ByteBuf::from(b"cat".to_vec())),
+                Item::List(vec![])
+            ])
+        );
+    }
+
+    #[test]
+    fn de_ignored_any() {
+        let (first, _, last): (u8, serde::de::IgnoredAny, u8) =
+            from_bytes(&[0x01, 0xc2, 0x02, 0x03, 0x04]).unwrap();
+        assert_eq!((first, last), (1, 4));
+    }
 }
@@ -0,0 +1,323 @@
+use crate::{ser::field_labels, RecursiveBytes, Rlp};
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::{format, vec::Vec};
+use core::fmt::{self, Display};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// One side of a [`Difference`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffValue {
+    /// The bytes of a string
+    Bytes(Vec<u8>),
+    /// A list, with its number of items
+    List(usize),
+}
+
+/// An item that differs between two `Rlp`.
+/// The path contains the index of the item at each nesting level, starting with the highest one.
+/// An empty path is the highest level itself, which differs in its number of items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    pub path: Vec<usize>,
+    /// Path of the serde field of the item, when diffing with a type
+    pub field: Option<String>,
+    pub left: DiffValue,
+    pub right: DiffValue,
+}
+
+/// Compare two `Rlp` item by item.
+/// Lists with a different number of items are reported, and their common items are still compared.
+/// An empty string and an empty list encoded as `0x80` are equal.
+pub fn diff_rlp(left: &Rlp, right: &Rlp) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    diff_lists(
+        left.0.iter(),
+        right.0.iter(),
+        &mut Vec::new(),
+        &mut differences,
+    );
+    differences
+}
+
+/// Same as `diff_rlp`, and label the differences with the serde field paths of `T`,
+/// such as `header.bloom` or `transactions[3]`.
+/// The labels are taken from decoding the left `Rlp` as a `T`, or the right one if it fails.
+/// The differences are not labeled if none of them can be decoded.
+pub fn diff_rlp_as<T>(left: &Rlp, right: &Rlp) -> Vec<Difference>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let mut differences = diff_rlp(left, right);
+    if differences.is_empty() {
+        return differences;
+    }
+
+    let labels = [left, right].into_iter().find_map(|rlp| {
+        let value = T::deserialize(&mut rlp.clone()).ok()?;
+        field_labels(&value).ok()
+    });
+    if let Some(labels) = labels {
+        for difference in differences.iter_mut() {
+            difference.field = label(&labels, &difference.path);
+        }
+    }
+
+    differences
+}
+
+/// label of the longest labeled prefix of `path`, followed by the rest of the path.
+fn label(labels: &BTreeMap<Vec<usize>, String>, path: &[usize]) -> Option<String> {
+    (1..=path.len()).rev().find_map(|len| {
+        let mut label = labels.get(&path[..len])?.clone();
+        for index in &path[len..] {
+            label.push_str(&format!("[{index}]"));
+        }
+        Some(label)
+    })
+}
+
+fn diff_lists<'a>(
+    left: impl ExactSizeIterator<Item = &'a RecursiveBytes>,
+    right: impl ExactSizeIterator<Item = &'a RecursiveBytes>,
+    path: &mut Vec<usize>,
+    differences: &mut Vec<Difference>,
+) {
+    if left.len() != right.len() {
+        differences.push(Difference {
+            path: path.clone(),
+            field: None,
+            left: DiffValue::List(left.len()),
+            right: DiffValue::List(right.len()),
+        });
+    }
+
+    for (index, (left, right)) in left.zip(right).enumerate() {
+        path.push(index);
+        diff_items(left, right, path, differences);
+        path.pop();
+    }
+}
+
+fn diff_items(
+    left: &RecursiveBytes,
+    right: &RecursiveBytes,
+    path: &mut Vec<usize>,
+    differences: &mut Vec<Difference>,
+) {
+    match (left, right) {
+        (RecursiveBytes::Nested(left), RecursiveBytes::Nested(right)) => {
+            diff_lists(left.iter(), right.iter(), path, differences)
+        }
+        _ => {
+            let (left, right) = (DiffValue::from(left), DiffValue::from(right));
+            if left != right {
+                differences.push(Difference {
+                    path: path.clone(),
+                    field: None,
+                    left,
+                    right,
+                });
+            }
+        }
+    }
+}
+
+impl From<&RecursiveBytes> for DiffValue {
+    fn from(value: &RecursiveBytes) -> Self {
+        match value {
+            RecursiveBytes::Bytes(bytes) => DiffValue::Bytes(bytes.to_vec()),
+            RecursiveBytes::EmptyList => DiffValue::Bytes(Vec::new()),
+            RecursiveBytes::Nested(recs) => DiffValue::List(recs.len()),
+        }
+    }
+}
+
+impl Display for DiffValue {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiffValue::Bytes(bytes) => {
+                formatter.write_str("0x")?;
+                for byte in bytes {
+                    write!(formatter, "{byte:02x}")?;
+                }
+                Ok(())
+            }
+            DiffValue::List(len) => write!(formatter, "list of {len} items"),
+        }
+    }
+}
+
+/// ```text
+/// [0][0][1] header.bloom: bytes differ, 0x00ff vs 0xff00
+/// [0][1] transactions: list length 2 vs 3
+/// ```
+impl Display for Difference {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            formatter.write_str("top level")?;
+        }
+        for index in self.path.iter() {
+            write!(formatter, "[{index}]")?;
+        }
+        if let Some(field) = &self.field {
+            write!(formatter, " {field}")?;
+        }
+        formatter.write_str(": ")?;
+
+        match (&self.left, &self.right) {
+            (DiffValue::List(left), DiffValue::List(right)) => {
+                write!(formatter, "list length {left} vs {right}")
+            }
+            (DiffValue::Bytes(_), DiffValue::Bytes(_)) => {
+                write!(formatter, "bytes differ, {} vs {}", self.left, self.right)
+            }
+            (left, right) => write!(formatter, "{left} vs {right}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_rlp, diff_rlp_as, DiffValue, Difference};
+    use crate::{to_bytes, unpack_rlp, RecursiveBytes, Rlp};
+    use serde::{Deserialize, Serialize};
+    use serde_bytes::ByteBuf;
+
+    #[test]
+    fn diff_items() {
+        // [["cat", "dog"], [1, 2, 3], 0x0f], [[]]
+        let left = unpack_rlp(&[
+            0xce, 0xc8, 0x83, b'c', b'a', b't', 0x83, b'd', b'o', b'g', 0xc3, 1, 2, 3, 0x0f, 0xc1,
+            0xc0,
+        ])
+        .unwrap();
+        // [["cat", "cow"], [1, 2, 3, 4], []], ""
+        let right = unpack_rlp(&[
+            0xcf, 0xc8, 0x83, b'c', b'a', b't', 0x83, b'c', b'o', b'w', 0xc4, 1, 2, 3, 4, 0xc0,
+            0x80,
+        ])
+        .unwrap();
+
+        let differences = diff_rlp(&left, &right);
+        assert_eq!(
+            differences,
+            [
+                Difference {
+                    path: vec![0, 0, 1],
+                    field: None,
+                    left: DiffValue::Bytes(b"dog".to_vec()),
+                    right: DiffValue::Bytes(b"cow".to_vec()),
+                },
+                Difference {
+                    path: vec![0, 1],
+                    field: None,
+                    left: DiffValue::List(3),
+                    right: DiffValue::List(4),
+                },
+                Difference {
+                    path: vec![0, 2],
+                    field: None,
+                    left: DiffValue::Bytes(vec![0x0f]),
+                    right: DiffValue::List(0),
+                },
+                Difference {
+                    path: vec![1],
+                    field: None,
+                    left: DiffValue::List(1),
+                    right: DiffValue::Bytes(vec![]),
+                },
+            ]
+        );
+
+        let lines: Vec<_> = differences.iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "[0][0][1]: bytes differ, 0x646f67 vs 0x636f77",
+                "[0][1]: list length 3 vs 4",
+                "[0][2]: 0x0f vs list of 0 items",
+                "[1]: list of 1 items vs 0x",
+            ]
+        );
+
+        assert!(diff_rlp(&left, &left).is_empty());
+        // the empty string and the serialized empty list are the same bytes
+        let empty_list = Rlp::new_unary(RecursiveBytes::EmptyList);
+        assert!(diff_rlp(&unpack_rlp(&[0x80]).unwrap(), &empty_list).is_empty());
+    }
+
+    #[test]
+    fn diff_top_level_length() {
+        let left = unpack_rlp(&[0x01, 0x02]).unwrap();
+        let right = unpack_rlp(&[0x01]).unwrap();
+
+        let differences = diff_rlp(&left, &right);
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].to_string(), "top level: list length 2 vs 1");
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Header {
+        number: u64,
+        bloom: ByteBuf,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Transaction {
+        nonce: u64,
+        data: ByteBuf,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Block {
+        header: Header,
+        transactions: Vec<Transaction>,
+    }
+
+    #[test]
+    fn diff_labeled() {
+        let transaction = |nonce| Transaction {
+            nonce,
+            data: ByteBuf::from(vec![nonce as u8; 3]),
+        };
+        let left = Block {
+            header: Header {
+                number: 1,
+                bloom: ByteBuf::from(vec![0x00, 0xff]),
+            },
+            transactions: vec![transaction(1), transaction(2)],
+        };
+        let right = Block {
+            header: Header {
+                number: 1,
+                bloom: ByteBuf::from(vec![0xff, 0x00]),
+            },
+            transactions: vec![transaction(1), transaction(3), transaction(4)],
+        };
+
+        let left = unpack_rlp(&to_bytes(&left).unwrap()).unwrap();
+        let right = unpack_rlp(&to_bytes(&right).unwrap()).unwrap();
+        let differences = diff_rlp_as::<Block>(&left, &right);
+
+        let lines: Vec<_> = differences.iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "[0][0][1] header.bloom: bytes differ, 0x00ff vs 0xff00",
+                "[0][1] transactions: list length 2 vs 3",
+                "[0][1][1][0] transactions[1].nonce: bytes differ, 0x02 vs 0x03",
+                "[0][1][1][1] transactions[1].data: bytes differ, 0x020202 vs 0x030303",
+            ]
+        );
+
+        // labels are taken from the right side when the left one can't be decoded
+        let invalid = unpack_rlp(&[0xc1, 0x80]).unwrap();
+        let differences = diff_rlp_as::<Block>(&invalid, &right);
+        assert_eq!(differences[0].to_string(), "[0]: list length 1 vs 2");
+        assert_eq!(
+            differences[1].to_string(),
+            "[0][0] header: 0x vs list of 2 items"
+        );
+    }
+}
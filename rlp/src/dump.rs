@@ -0,0 +1,144 @@
+use crate::{pack_rlp, read_item_header, PrefixKind, Rlp, RlpError};
+use alloc::string::String;
+use core::fmt::{self, Display, Write};
+
+impl Display for PrefixKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PrefixKind::Byte => formatter.write_str("byte"),
+            PrefixKind::ShortString => formatter.write_str("short string"),
+            PrefixKind::LongString => formatter.write_str("long string"),
+            PrefixKind::ShortList => formatter.write_str("short list"),
+            PrefixKind::LongList => formatter.write_str("long list"),
+        }
+    }
+}
+
+/// Pretty print RLP-encoded bytes as an indented tree, one item per line.
+/// Each line contains the offset of the item, its prefix bytes, the kind of prefix,
+/// the length of the payload and, for strings, the payload in hex.
+/// The payload is also shown as ASCII when all of its bytes are printable.
+///
+/// ```text
+/// 000000  c8 short list len=8
+/// 000001    83 short string len=3 0x636174 "cat"
+/// 000005    83 short string len=3 0x646f67 "dog"
+/// ```
+pub fn dump(bytes: &[u8]) -> Result<String, RlpError> {
+    let mut out = String::new();
+    dump_element(&mut out, bytes, 0, 0)?;
+    Ok(out)
+}
+
+fn dump_element(
+    out: &mut String,
+    bytes: &[u8],
+    mut cursor: usize,
+    depth: usize,
+) -> Result<(), RlpError> {
+    while cursor < bytes.len() {
+        let header = read_item_header(bytes, cursor)?;
+        let prefix = match header.kind {
+            PrefixKind::Byte => &bytes[cursor..header.payload.end],
+            _ => &bytes[cursor..header.payload.start],
+        };
+
+        let _ = write!(out, "{cursor:06x}  {:indent$}", "", indent = depth * 2);
+        for (i, byte) in prefix.iter().enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+            let _ = write!(out, "{byte:02x}");
+        }
+        let _ = write!(out, " {} len={}", header.kind, header.payload.len());
+
+        if header.kind.is_list() {
+            out.push('\n');
+            dump_element(
+                out,
+                &bytes[..header.payload.end],
+                header.payload.start,
+                depth + 1,
+            )?;
+        } else {
+            let payload = &bytes[header.payload.clone()];
+            if !payload.is_empty() {
+                out.push_str(" 0x");
+                for byte in payload {
+                    let _ = write!(out, "{byte:02x}");
+                }
+                if payload.iter().all(|b| (0x20..=0x7e).contains(b)) {
+                    let _ = write!(out, " \"{}\"", String::from_utf8_lossy(payload));
+                }
+            }
+            out.push('\n');
+        }
+
+        cursor = header.payload.end;
+    }
+
+    Ok(())
+}
+
+/// Trees that cannot be dumped are rendered as an error marker,
+/// so that `to_string` never panics.
+impl Display for Rlp {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match pack_rlp(self.clone()).and_then(|bytes| dump(&bytes)) {
+            Ok(dumped) => formatter.write_str(&dumped),
+            Err(err) => write!(formatter, "<invalid rlp: {err}>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dump;
+    use crate::{to_bytes, unpack_rlp, RlpError};
+
+    #[test]
+    fn dump_cat_dog() {
+        let bytes = to_bytes(&vec!["cat", "dog"]).unwrap();
+
+        assert_eq!(
+            dump(&bytes).unwrap(),
+            "000000  c8 short list len=8\n\
+             000001    83 short string len=3 0x636174 \"cat\"\n\
+             000005    83 short string len=3 0x646f67 \"dog\"\n"
+        );
+    }
+
+    #[test]
+    fn dump_nested_long() {
+        // [[], 0x0f, ""], 0x01 * 56
+        let mut bytes = vec![0xc3, 0xc0, 0x0f, 0x80, 0xb8, 56];
+        bytes.extend_from_slice(&[0x01; 56]);
+        let dumped = dump(&bytes).unwrap();
+        let lines: Vec<_> = dumped.lines().collect();
+
+        assert_eq!(
+            lines,
+            [
+                "000000  c3 short list len=3",
+                "000001    c0 short list len=0",
+                "000002    0f byte len=1 0x0f",
+                "000003    80 short string len=0",
+                &format!("000004  b8 38 long string len=56 0x{}", "01".repeat(56)),
+            ]
+        );
+    }
+
+    #[test]
+    fn dump_invalid() {
+        assert!(matches!(dump(&[0x81, 0x01]), Err(RlpError::InvalidBytes)));
+        assert!(matches!(dump(&[0xc2, 0x01]), Err(RlpError::MissingBytes)));
+    }
+
+    #[test]
+    fn display_rlp() {
+        let bytes = to_bytes(&vec!["cat", "dog"]).unwrap();
+        let rlp = unpack_rlp(&bytes).unwrap();
+
+        assert_eq!(rlp.to_string(), dump(&bytes).unwrap());
+    }
+}
@@ -0,0 +1,175 @@
+//! Conversion between the `Rlp` representation and JSON, in the style of the Ethereum tests.
+//!
+//! - A list is a JSON array.
+//! - A string starting with `0x` is hex-encoded bytes.
+//! - A string starting with `#` is a decimal big integer, encoded in big-endian without leading zeros.
+//! - Any other string is taken as its UTF-8 bytes.
+//! - A non-negative JSON number is encoded in big-endian without leading zeros.
+//!
+//! Bytes are always converted back to JSON as `0x` hex strings, so that no information is lost.
+
+use crate::{RecursiveBytes, Rlp, RlpError, SmallBytes};
+use alloc::string::String;
+use alloc::{format, vec::Vec};
+use serde_json::Value;
+
+impl RecursiveBytes {
+    pub fn to_json(&self) -> Value {
+        match self {
+            RecursiveBytes::Bytes(bytes) => Value::String(format!("0x{}", hex::encode(bytes))),
+            RecursiveBytes::EmptyList => Value::String(String::from("0x")),
+            RecursiveBytes::Nested(recs) => {
+                Value::Array(recs.iter().map(RecursiveBytes::to_json).collect())
+            }
+        }
+    }
+
+    pub fn from_json(value: &Value) -> Result<Self, RlpError> {
+        let rec = match value {
+            Value::Array(values) => RecursiveBytes::Nested(
+                values
+                    .iter()
+                    .map(RecursiveBytes::from_json)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::String(string) => {
+                let bytes = if let Some(hex_string) = string.strip_prefix("0x") {
+                    hex::decode(hex_string).map_err(|_| RlpError::InvalidBytes)?
+                } else if let Some(digits) = string.strip_prefix('#') {
+                    decimal_to_be_bytes(digits).ok_or(RlpError::InvalidBytes)?
+                } else {
+                    string.as_bytes().to_vec()
+                };
+                RecursiveBytes::Bytes(bytes.into())
+            }
+            Value::Number(number) => {
+                let num = number.as_u64().ok_or(RlpError::InvalidBytes)?;
+                let bytes = num.to_be_bytes();
+                let index = bytes.iter().position(|b| b > &0).unwrap_or(bytes.len());
+                RecursiveBytes::Bytes(SmallBytes::from_slice(&bytes[index..]))
+            }
+            Value::Null | Value::Bool(_) | Value::Object(_) => return Err(RlpError::InvalidBytes),
+        };
+
+        Ok(rec)
+    }
+}
+
+impl Rlp {
+    /// Convert the single item of this `Rlp` to JSON.
+    pub fn to_json(&self) -> Result<Value, RlpError> {
+        match self.len() {
+            0 => Err(RlpError::MissingBytes),
+            1 => Ok(self.0[0].to_json()),
+            _ => Err(RlpError::TrailingBytes),
+        }
+    }
+
+    pub fn from_json(value: &Value) -> Result<Self, RlpError> {
+        RecursiveBytes::from_json(value).map(Rlp::new_unary)
+    }
+}
+
+fn decimal_to_be_bytes(digits: &str) -> Option<Vec<u8>> {
+    if digits.is_empty() {
+        return None;
+    }
+
+    let mut bytes: Vec<u8> = Vec::new();
+    for digit in digits.chars() {
+        let mut carry = digit.to_digit(10)?;
+        for byte in bytes.iter_mut().rev() {
+            let val = *byte as u32 * 10 + carry;
+            *byte = val as u8;
+            carry = val >> 8;
+        }
+        if carry > 0 {
+            bytes.insert(0, carry as u8);
+        }
+    }
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{pack_rlp, unpack_rlp, RecursiveBytes, Rlp, RlpError};
+    use serde_json::json;
+
+    #[test]
+    fn json_to_rlp() {
+        let value = json!(["dog", "0x0400", 1024, "#256", 0, [[], "0x"]]);
+
+        let rlp = Rlp::from_json(&value).unwrap();
+        let bytes = pack_rlp(rlp).unwrap();
+
+        assert_eq!(
+            bytes,
+            [
+                0xd1, 0x83, b'd', b'o', b'g', 0x82, 0x04, 0x00, 0x82, 0x04, 0x00, 0x82, 0x01, 0x00,
+                0x80, 0xc2, 0xc0, 0x80
+            ]
+        );
+    }
+
+    #[test]
+    fn rlp_to_json() {
+        let rlp = unpack_rlp(&[0xc7, 0x83, b'd', b'o', b'g', 0xc1, 0x00, 0x80]).unwrap();
+
+        assert_eq!(rlp.to_json().unwrap(), json!(["0x646f67", ["0x00"], "0x"]));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let value = json!(["0x", ["0x01", ["0x"]], format!("0x{}", "ff".repeat(60))]);
+
+        let bytes = pack_rlp(Rlp::from_json(&value).unwrap()).unwrap();
+        let rlp = unpack_rlp(&bytes).unwrap();
+
+        assert_eq!(rlp.to_json().unwrap(), value);
+    }
+
+    #[test]
+    fn json_bigint() {
+        let rec = RecursiveBytes::from_json(&json!(
+            "#115792089237316195423570985008687907853269984665640564039457584007913129639936"
+        ))
+        .unwrap();
+
+        let mut expected = vec![0x01];
+        expected.extend_from_slice(&[0; 32]);
+        assert_eq!(rec, RecursiveBytes::Bytes(expected.into()));
+
+        assert_eq!(
+            RecursiveBytes::from_json(&json!("#0")).unwrap(),
+            RecursiveBytes::Bytes(vec![].into())
+        );
+    }
+
+    #[test]
+    fn json_invalid() {
+        for value in [
+            json!(-1),
+            json!(1.5),
+            json!(null),
+            json!({ "a": "b" }),
+            json!("0xzz"),
+            json!("#12a"),
+            json!([true]),
+        ] {
+            assert!(matches!(
+                RecursiveBytes::from_json(&value),
+                Err(RlpError::InvalidBytes)
+            ));
+        }
+
+        assert!(matches!(
+            Rlp::default().to_json(),
+            Err(RlpError::MissingBytes)
+        ));
+        assert!(matches!(
+            unpack_rlp(&[0x01, 0x02]).unwrap().to_json(),
+            Err(RlpError::TrailingBytes)
+        ));
+    }
+}
@@ -0,0 +1,279 @@
+//! Control over which values are encoded as a list of their own.
+//!
+//! By default, structs, tuple structs and sequences are lists, while tuples are inlined
+//! in the enclosing list. The wrappers of this module override the default for one field,
+//! and are honored the same way when serializing and deserializing:
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! use rlp_rs::{AsList, Inline};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Common {
+//!     number: u8,
+//!     time: u8,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Header {
+//!     common: Inline<Common>,
+//!     base_fee: u8,
+//!     pair: AsList<(u8, u8)>,
+//! }
+//!
+//! let header = Header {
+//!     common: Inline(Common { number: 1, time: 2 }),
+//!     base_fee: 3,
+//!     pair: AsList((4, 5)),
+//! };
+//! let bytes = rlp_rs::to_bytes(&header).unwrap();
+//! // [1, 2, 3, [4, 5]]
+//! assert_eq!(bytes, [0xc6, 0x01, 0x02, 0x03, 0xc2, 0x04, 0x05]);
+//! assert_eq!(rlp_rs::from_bytes::<Header>(&bytes).unwrap(), header);
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::{Serialize, Serializer};
+
+/// serde name of the newtype struct `AsList` is serialized as
+pub(crate) const AS_LIST: &str = "$rlp_rs::AsList";
+/// serde name of the newtype struct `Inline` is serialized as
+pub(crate) const INLINE: &str = "$rlp_rs::Inline";
+
+/// Wraps the items of the value in a list, typically a tuple that would otherwise be inlined.
+/// A struct is wrapped in a second list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AsList<T>(pub T);
+
+/// Writes the items of the value directly in the enclosing list,
+/// typically the fields of a struct that is part of a larger one.
+/// An inlined sequence takes all the remaining items of the enclosing list when deserializing.
+/// Serializing any other value, such as an integer or an enum, fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Inline<T>(pub T);
+
+impl<T: Serialize> Serialize for AsList<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(AS_LIST, &self.0)
+    }
+}
+
+impl<T: Serialize> Serialize for Inline<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(INLINE, &self.0)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for AsList<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_newtype_struct(AS_LIST, WrapperVisitor(PhantomData))
+            .map(AsList)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Inline<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_newtype_struct(INLINE, WrapperVisitor(PhantomData))
+            .map(Inline)
+    }
+}
+
+struct WrapperVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for WrapperVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a newtype struct")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsList, Inline};
+    use crate::{diff_rlp_as, from_bytes, size_report, to_bytes, unpack_rlp, RlpError};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Common {
+        number: u8,
+        time: u16,
+    }
+
+    #[test]
+    fn inline_struct() {
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        struct Header {
+            common: Inline<Common>,
+            base_fee: u8,
+        }
+
+        let header = Header {
+            common: Inline(Common {
+                number: 1,
+                time: 1024,
+            }),
+            base_fee: 7,
+        };
+        let bytes = to_bytes(&header).unwrap();
+        assert_eq!(bytes, [0xc5, 0x01, 0x82, 0x04, 0x00, 0x07]);
+        assert_eq!(from_bytes::<Header>(&bytes).unwrap(), header);
+
+        // the fields of the inlined struct are missing
+        assert!(from_bytes::<Header>(&[0xc2, 0x01, 0x07]).is_err());
+        assert!(matches!(
+            from_bytes::<Header>(&[0xc6, 0x01, 0x82, 0x04, 0x00, 0x07, 0x08]),
+            Err(RlpError::InvalidLength)
+        ));
+
+        let report = size_report(&header).unwrap();
+        let common = report.get("common").unwrap();
+        // no list prefix of its own
+        assert_eq!((common.prefix, common.payload), (1, 3));
+        assert_eq!(report.get("common.time").unwrap().payload, 2);
+
+        let mut other = header.clone();
+        other.common.0.time = 1025;
+        let differences = diff_rlp_as::<Header>(
+            &unpack_rlp(&bytes).unwrap(),
+            &unpack_rlp(&to_bytes(&other).unwrap()).unwrap(),
+        );
+        assert_eq!(
+            differences[0].to_string(),
+            "[0][1] common.time: bytes differ, 0x0400 vs 0x0401"
+        );
+    }
+
+    #[test]
+    fn layouts() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Layouts {
+            tuple: (u8, u8),
+            list: AsList<(u8, u8)>,
+            inlined_list: Inline<AsList<(u8, u8)>>,
+            double: AsList<Common>,
+            rest: Inline<Vec<u8>>,
+        }
+
+        let layouts = Layouts {
+            tuple: (1, 2),
+            list: AsList((3, 4)),
+            inlined_list: Inline(AsList((5, 6))),
+            double: AsList(Common { number: 7, time: 8 }),
+            rest: Inline(vec![9, 10]),
+        };
+        let bytes = to_bytes(&layouts).unwrap();
+        #[rustfmt::skip]
+        assert_eq!(
+            bytes,
+            [0xcd, 0x01, 0x02, 0xc2, 0x03, 0x04, 0x05, 0x06, 0xc3, 0xc2, 0x07, 0x08, 0x09, 0x0a]
+        );
+        assert_eq!(from_bytes::<Layouts>(&bytes).unwrap(), layouts);
+
+        let tests: [(&[u8], RlpError); 3] = [
+            (&[0x01, 0x02], RlpError::ExpectedList),
+            (&[0xc1, 0x01], RlpError::MissingBytes),
+            (&[0xc3, 0x01, 0x02, 0x03], RlpError::InvalidLength),
+        ];
+
+        for (i, (bytes, err)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            let res = from_bytes::<AsList<(u8, u8)>>(bytes);
+            assert_eq!(
+                core::mem::discriminant(&res.unwrap_err()),
+                core::mem::discriminant(&err)
+            );
+
+            println!("ok");
+        }
+    }
+
+    #[test]
+    fn inline_not_list() {
+        #[derive(Debug, Serialize)]
+        enum Kind {
+            Unit,
+            Value(Common),
+        }
+
+        #[derive(Debug, Serialize)]
+        struct Wrapped<T> {
+            first: u8,
+            inlined: Inline<T>,
+        }
+
+        let inline_error = |res: Result<Vec<u8>, RlpError>| {
+            let Err(RlpError::Message(message)) = res else {
+                panic!("expected a message, got {res:?}");
+            };
+            assert!(message.contains("`Inline`"), "{message}");
+        };
+
+        inline_error(to_bytes(&Inline(Kind::Unit)));
+        inline_error(to_bytes(&Wrapped {
+            first: 1,
+            inlined: Inline(Kind::Value(Common { number: 2, time: 3 })),
+        }));
+        inline_error(to_bytes(&Wrapped {
+            first: 1,
+            inlined: Inline(2u8),
+        }));
+        inline_error(to_bytes(&Wrapped {
+            first: 1,
+            inlined: Inline(()),
+        }));
+
+        // still applies through a newtype struct
+        #[derive(Debug, Serialize)]
+        struct Newtype(Common);
+        let bytes = to_bytes(&Wrapped {
+            first: 1,
+            inlined: Inline(Newtype(Common { number: 2, time: 3 })),
+        })
+        .unwrap();
+        assert_eq!(bytes, [0xc3, 0x01, 0x02, 0x03]);
+    }
+
+    #[test]
+    fn tuple_in_struct() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Pairs {
+            first: (u8, (u8, u8)),
+            second: [u8; 2],
+            last: u8,
+        }
+
+        let pairs = Pairs {
+            first: (1, (2, 3)),
+            second: [4, 5],
+            last: 6,
+        };
+        let bytes = to_bytes(&pairs).unwrap();
+        assert_eq!(bytes, [0xc6, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        assert_eq!(from_bytes::<Pairs>(&bytes).unwrap(), pairs);
+    }
+}
@@ -1,29 +1,78 @@
-use std::collections::VecDeque;
-use std::fmt::{self, Display};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::{vec, vec::Vec};
+use core::fmt::{self, Display};
+
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "bytes")]
+pub use buf::{from_bytes_shared, pack_rlp_buf, to_buf, unpack_rlp_shared};
+
+#[cfg(feature = "codec")]
+mod codec;
+#[cfg(feature = "codec")]
+pub use codec::{CodecError, RlpCodec, TypedRlpCodec, DEFAULT_MAX_FRAME_LEN};
 
 mod de;
 pub use de::from_bytes;
 
+mod diff;
+pub use diff::{diff_rlp, diff_rlp_as, DiffValue, Difference};
+
+mod dump;
+pub use dump::dump;
+
+#[cfg(feature = "json")]
+mod json;
+
+mod layout;
+pub use layout::{AsList, Inline};
+
+pub mod schema;
+
 mod ser;
 pub use ser::to_bytes;
 
+pub mod signed;
+
+mod size;
+pub use size::{size_report, SizeReport};
+
+mod small;
+pub use small::SmallBytes;
+
+#[cfg(feature = "proptest")]
+pub mod strategy;
+
+mod visit;
+pub use visit::{visit_rlp, RlpVisitor};
+
 #[doc(hidden)]
 /// This low-level function is used to convert an rlp representation into bytes
-pub fn pack_rlp(mut rlp: Rlp) -> Result<Vec<u8>, RlpError> {
-    let mut pack = Vec::new();
-    while let Some(rec) = rlp.pop_front() {
-        recursive_pack_rlp(&mut pack, rec)?;
-    }
+pub fn pack_rlp(rlp: Rlp) -> Result<Vec<u8>, RlpError> {
+    let mut pack = Vec::with_capacity(rlp.0.iter().map(RecursiveBytes::encoded_len).sum());
+    pack_rlp_into(&mut pack, &rlp)?;
     Ok(pack)
 }
 
+pub(crate) fn pack_rlp_into<S: Sink>(pack: &mut S, rlp: &Rlp) -> Result<(), RlpError> {
+    for rec in rlp.0.iter() {
+        recursive_pack_rlp(pack, rec)?;
+    }
+    Ok(())
+}
+
 #[cfg_attr(test, derive(PartialEq))]
 #[derive(Debug, Clone)]
 #[doc(hidden)]
 /// Convert
 pub enum RecursiveBytes {
     /// Bytes (string)
-    Bytes(Vec<u8>),
+    Bytes(SmallBytes),
     /// An empty list that should serialize to 0x80
     EmptyList,
     /// A nested data structure to represent arbitrarily arbitrarily nested arrays (list)
@@ -33,10 +82,13 @@ pub enum RecursiveBytes {
 #[doc(hidden)]
 /// This low-level function is used to convert bytes into an rlp representation
 pub fn unpack_rlp(bytes: &[u8]) -> Result<Rlp, RlpError> {
+    if bytes.is_empty() {
+        return Err(RlpError::MissingBytes);
+    }
     Ok(Rlp::new(unpack_rlp_element(bytes, 0)?.into()))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RlpError {
     MissingBytes,
     TrailingBytes,
@@ -73,6 +125,7 @@ impl Display for RlpError {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for RlpError {}
 
 impl RecursiveBytes {
@@ -99,7 +152,7 @@ impl IntoIterator for Rlp {
     }
 }
 
-pub struct RlpIntoIter(std::collections::vec_deque::IntoIter<RecursiveBytes>);
+pub struct RlpIntoIter(alloc::collections::vec_deque::IntoIter<RecursiveBytes>);
 
 impl Iterator for RlpIntoIter {
     type Item = Rlp;
@@ -156,94 +209,238 @@ impl Rlp {
         };
         Ok(recs)
     }
-}
 
-fn unpack_rlp_element(bytes: &[u8], mut cursor: usize) -> Result<Vec<RecursiveBytes>, RlpError> {
-    let mut unpacked = Vec::new();
-
-    while let Some(disc) = bytes.get(cursor) {
-        let disc = *disc;
-        cursor += 1;
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut RecursiveBytes> {
+        self.0.get_mut(index)
+    }
 
-        let ret = if disc <= 0x7f {
-            let ret = bytes.get((cursor - 1)..cursor).unwrap();
+    pub fn get_nested_mut(&mut self, index: usize) -> Result<&mut Vec<RecursiveBytes>, RlpError> {
+        let RecursiveBytes::Nested(recs) = self.0.get_mut(index).ok_or(RlpError::MissingBytes)?
+        else {
+            return Err(RlpError::ExpectedList);
+        };
+        Ok(recs)
+    }
 
-            RecursiveBytes::Bytes(ret.to_vec())
-        } else if disc <= 0xb7 {
-            let len = disc - 0x80;
-            let ret = bytes
-                .get(cursor..(cursor + len as usize))
-                .ok_or(RlpError::MissingBytes)?;
+    /// Get the item addressed by `path`.
+    /// The first index selects a top-level item, every following one selects
+    /// an item of the list found at the previous step.
+    pub fn get_path(&self, path: &[usize]) -> Option<&RecursiveBytes> {
+        let (first, rest) = path.split_first()?;
+        rest.iter()
+            .try_fold(self.0.get(*first)?, |rec, index| match rec {
+                RecursiveBytes::Nested(recs) => recs.get(*index),
+                _ => None,
+            })
+    }
 
-            if len == 1 && ret[0] <= 127 {
-                return Err(RlpError::InvalidBytes);
-            }
+    pub fn get_path_mut(&mut self, path: &[usize]) -> Option<&mut RecursiveBytes> {
+        self.path_mut(path).ok()
+    }
 
-            cursor += len as usize;
+    /// Replace the item at `path`, returning the previous one.
+    pub fn set(&mut self, path: &[usize], rec: RecursiveBytes) -> Result<RecursiveBytes, RlpError> {
+        let slot = self.path_mut(path)?;
+        Ok(core::mem::replace(slot, rec))
+    }
 
-            RecursiveBytes::Bytes(ret.to_vec())
-        } else if disc <= 0xbf {
-            let len_bytes_len = disc - 0xb7;
-            if len_bytes_len > 8 {
-                // unimplemented!("we do not support > 2**64 bytes long strings");
-                return Err(RlpError::InvalidLength);
+    /// Insert an item at `path`, shifting all the items after it.
+    /// The last index of the path may be equal to the length of the list to append.
+    pub fn insert(&mut self, path: &[usize], rec: RecursiveBytes) -> Result<(), RlpError> {
+        let (index, parent) = path.split_last().ok_or(RlpError::MissingBytes)?;
+        let index = *index;
+        if parent.is_empty() {
+            if index > self.0.len() {
+                return Err(RlpError::MissingBytes);
             }
-            let mut len_bytes_base = [0; 8];
-            let len_bytes = bytes
-                .get(cursor..(cursor + len_bytes_len as usize))
-                .ok_or(RlpError::MissingBytes)?;
-            if len_bytes.starts_with(&[0]) {
-                return Err(RlpError::TrailingBytes);
+            self.0.insert(index, rec);
+        } else {
+            let recs = self.nested_mut(parent)?;
+            if index > recs.len() {
+                return Err(RlpError::MissingBytes);
             }
-            cursor += len_bytes_len as usize;
+            recs.insert(index, rec);
+        }
+        Ok(())
+    }
 
-            len_bytes_base[(8 - len_bytes.len())..].copy_from_slice(len_bytes);
-            let len = usize::from_be_bytes(len_bytes_base);
-            if len <= 55 {
-                return Err(RlpError::InvalidLength);
+    /// Remove the item at `path`, shifting all the items after it.
+    pub fn remove(&mut self, path: &[usize]) -> Result<RecursiveBytes, RlpError> {
+        let (index, parent) = path.split_last().ok_or(RlpError::MissingBytes)?;
+        let index = *index;
+        if parent.is_empty() {
+            self.0.remove(index).ok_or(RlpError::MissingBytes)
+        } else {
+            let recs = self.nested_mut(parent)?;
+            if index >= recs.len() {
+                return Err(RlpError::MissingBytes);
             }
-            let max_cursor = cursor.checked_add(len).ok_or(RlpError::InvalidLength)?;
-            let ret = bytes
-                .get(cursor..max_cursor)
-                .ok_or(RlpError::MissingBytes)?;
-            cursor += len;
-
-            RecursiveBytes::Bytes(ret.to_vec())
-        } else if disc <= 0xf7 {
-            let len = disc - 0xc0;
-            let list_bytes = bytes
-                .get(cursor..(cursor + len as usize))
-                .ok_or(RlpError::MissingBytes)?;
-            cursor += len as usize;
+            Ok(recs.remove(index))
+        }
+    }
 
-            // we want to represent empty lists so don't remove them
-            RecursiveBytes::Nested(unpack_rlp_element(list_bytes, 0)?)
+    /// Append an item to the list at `path`. An empty path appends a top-level item.
+    pub fn push(&mut self, path: &[usize], rec: RecursiveBytes) -> Result<(), RlpError> {
+        if path.is_empty() {
+            self.0.push_back(rec);
         } else {
-            let len_bytes_len = disc - 0xf7;
-            let mut len_bytes_base = [0; 8];
-            let len_bytes = bytes
-                .get(cursor..(cursor + len_bytes_len as usize))
-                .ok_or(RlpError::MissingBytes)?;
-
-            if len_bytes[0] == 0 {
-                return Err(RlpError::TrailingBytes);
-            }
+            self.nested_mut(path)?.push(rec);
+        }
+        Ok(())
+    }
+
+    /// Shorten the list at `path` to `len` items. An empty path truncates the top-level items.
+    pub fn truncate(&mut self, path: &[usize], len: usize) -> Result<(), RlpError> {
+        if path.is_empty() {
+            self.0.truncate(len);
+        } else {
+            self.nested_mut(path)?.truncate(len);
+        }
+        Ok(())
+    }
 
-            cursor += len_bytes_len as usize;
-            len_bytes_base[(8 - len_bytes.len())..].copy_from_slice(len_bytes);
+    fn path_mut(&mut self, path: &[usize]) -> Result<&mut RecursiveBytes, RlpError> {
+        let (first, rest) = path.split_first().ok_or(RlpError::MissingBytes)?;
+        let mut rec = self.0.get_mut(*first).ok_or(RlpError::MissingBytes)?;
+        for index in rest {
+            let RecursiveBytes::Nested(recs) = rec else {
+                return Err(RlpError::ExpectedList);
+            };
+            rec = recs.get_mut(*index).ok_or(RlpError::MissingBytes)?;
+        }
+        Ok(rec)
+    }
 
-            let len = usize::from_be_bytes(len_bytes_base);
-            if len < 55 {
-                return Err(RlpError::InvalidLength);
-            }
+    fn nested_mut(&mut self, path: &[usize]) -> Result<&mut Vec<RecursiveBytes>, RlpError> {
+        let RecursiveBytes::Nested(recs) = self.path_mut(path)? else {
+            return Err(RlpError::ExpectedList);
+        };
+        Ok(recs)
+    }
+}
 
-            let max_cursor = cursor.checked_add(len).ok_or(RlpError::InvalidLength)?; // TODO wrong error
-            let list_bytes = bytes
-                .get(cursor..max_cursor)
-                .ok_or(RlpError::MissingBytes)?;
-            cursor += len;
+/// The kind of an item, as told by its first byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PrefixKind {
+    /// A single byte below 0x80 that is its own payload
+    Byte,
+    /// A string of at most 55 bytes
+    ShortString,
+    /// A string of more than 55 bytes, its length is prefixed
+    LongString,
+    /// A list with a payload of at most 55 bytes
+    ShortList,
+    /// A list with a payload of more than 55 bytes, its length is prefixed
+    LongList,
+}
+
+impl PrefixKind {
+    pub(crate) fn is_list(&self) -> bool {
+        matches!(self, PrefixKind::ShortList | PrefixKind::LongList)
+    }
+}
+
+/// The prefix of an item, read without touching its payload.
+pub(crate) struct ItemHeader {
+    pub(crate) kind: PrefixKind,
+    /// Range of the payload in the input
+    pub(crate) payload: core::ops::Range<usize>,
+}
+
+/// Read the prefix of the item starting at `cursor` and make sure that its payload fits in `bytes`.
+pub(crate) fn read_item_header(bytes: &[u8], cursor: usize) -> Result<ItemHeader, RlpError> {
+    let header = read_item_prefix(bytes, cursor)?;
+    let payload = bytes
+        .get(header.payload.clone())
+        .ok_or(RlpError::MissingBytes)?;
+
+    if header.kind == PrefixKind::ShortString && payload.len() == 1 && payload[0] <= 127 {
+        return Err(RlpError::InvalidBytes);
+    }
 
-            RecursiveBytes::Nested(unpack_rlp_element(list_bytes, 0)?)
+    Ok(header)
+}
+
+/// Read the prefix of the item starting at `cursor`, including the length of a long item,
+/// without checking that the declared payload fits in `bytes`.
+pub(crate) fn read_item_prefix(bytes: &[u8], cursor: usize) -> Result<ItemHeader, RlpError> {
+    let disc = *bytes.get(cursor).ok_or(RlpError::MissingBytes)?;
+    let start = cursor + 1;
+
+    let header = if disc <= 0x7f {
+        ItemHeader {
+            kind: PrefixKind::Byte,
+            payload: cursor..start,
+        }
+    } else if disc <= 0xb7 {
+        ItemHeader {
+            kind: PrefixKind::ShortString,
+            payload: start..(start + (disc - 0x80) as usize),
+        }
+    } else if disc <= 0xbf {
+        ItemHeader {
+            kind: PrefixKind::LongString,
+            payload: read_long_payload(bytes, start, disc - 0xb7, 56)?,
+        }
+    } else if disc <= 0xf7 {
+        ItemHeader {
+            kind: PrefixKind::ShortList,
+            payload: start..(start + (disc - 0xc0) as usize),
+        }
+    } else {
+        ItemHeader {
+            kind: PrefixKind::LongList,
+            payload: read_long_payload(bytes, start, disc - 0xf7, 56)?,
+        }
+    };
+
+    Ok(header)
+}
+
+fn read_long_payload(
+    bytes: &[u8],
+    mut cursor: usize,
+    len_bytes_len: u8,
+    min_len: usize,
+) -> Result<core::ops::Range<usize>, RlpError> {
+    if len_bytes_len > 8 {
+        // unimplemented!("we do not support > 2**64 bytes long items");
+        return Err(RlpError::InvalidLength);
+    }
+    let mut len_bytes_base = [0; 8];
+    let len_bytes = bytes
+        .get(cursor..(cursor + len_bytes_len as usize))
+        .ok_or(RlpError::MissingBytes)?;
+    // a length with leading zeros is not the shortest one
+    if len_bytes.starts_with(&[0]) {
+        return Err(RlpError::InvalidLength);
+    }
+    cursor += len_bytes_len as usize;
+
+    len_bytes_base[(8 - len_bytes.len())..].copy_from_slice(len_bytes);
+    let len =
+        usize::try_from(u64::from_be_bytes(len_bytes_base)).map_err(|_| RlpError::InvalidLength)?;
+    if len < min_len {
+        return Err(RlpError::InvalidLength);
+    }
+
+    let max_cursor = cursor.checked_add(len).ok_or(RlpError::InvalidLength)?;
+    Ok(cursor..max_cursor)
+}
+
+fn unpack_rlp_element(bytes: &[u8], mut cursor: usize) -> Result<Vec<RecursiveBytes>, RlpError> {
+    let mut unpacked = Vec::new();
+
+    while cursor < bytes.len() {
+        let header = read_item_header(bytes, cursor)?;
+        let payload = &bytes[header.payload.clone()];
+        cursor = header.payload.end;
+
+        let ret = if header.kind.is_list() {
+            // we want to represent empty lists so don't remove them
+            RecursiveBytes::Nested(unpack_rlp_element(payload, 0)?)
+        } else {
+            RecursiveBytes::Bytes(SmallBytes::from_slice(payload))
         };
 
         unpacked.push(ret);
@@ -259,69 +456,94 @@ fn parse_num<const N: usize>(bytes: [u8; N]) -> Option<Vec<u8>> {
         .map(|index| bytes[index..].to_vec())
 }
 
-fn append_rlp_bytes(pack: &mut Vec<u8>, new_bytes: Vec<u8>) -> Result<usize, RlpError> {
-    let mut bytes = match new_bytes.len() {
-        1 if new_bytes[0] <= 127 => new_bytes,
-        len => {
-            if len <= 55 {
-                if len == 1 && new_bytes[0] <= 127 {
-                    return Err(RlpError::InvalidBytes);
-                }
-                let disc = 0x80 + len as u8;
-                let mut bytes = vec![disc];
-                bytes.extend_from_slice(&new_bytes);
-                bytes
-            } else if len as u64 <= u64::MAX {
-                let mut len_bytes = parse_num((len as u64).to_be_bytes())
-                    .expect("fine because the length exceeds 55");
-                let disc = 0xb7 + len_bytes.len() as u8;
-                let mut bytes = vec![disc];
-                bytes.append(&mut len_bytes);
-                bytes.extend_from_slice(&new_bytes);
-                bytes
-            } else {
-                return Err(RlpError::InvalidLength);
-            }
-        }
-    };
+/// Where the encoder writes: a `Vec`, or any `BufMut` with the `bytes` feature.
+pub(crate) trait Sink {
+    fn put_u8(&mut self, byte: u8);
+
+    fn put_slice(&mut self, bytes: &[u8]);
+}
+
+impl Sink for Vec<u8> {
+    fn put_u8(&mut self, byte: u8) {
+        self.push(byte);
+    }
 
-    let len = bytes.len();
+    fn put_slice(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
 
-    pack.append(&mut bytes);
+fn append_rlp_bytes<S: Sink>(pack: &mut S, new_bytes: &[u8]) -> Result<usize, RlpError> {
+    match new_bytes {
+        [byte] if *byte <= 127 => {
+            pack.put_u8(*byte);
+            Ok(1)
+        }
+        _ => {
+            let len = append_header(pack, new_bytes.len(), 0x80);
+            pack.put_slice(new_bytes);
+            Ok(len + new_bytes.len())
+        }
+    }
+}
 
-    Ok(len)
+/// Length of the prefix of a string or list with a payload of `len` bytes.
+pub(crate) fn header_len(len: usize) -> usize {
+    if len <= 55 {
+        1
+    } else {
+        1 + (usize::BITS - len.leading_zeros()).div_ceil(8) as usize
+    }
 }
 
-fn serialize_list_len(len: usize) -> Result<Vec<u8>, RlpError> {
-    let bytes = if len <= 55 {
-        vec![0xc0 + len as u8]
+/// Write the prefix of a string (`offset` 0x80) or a list (`offset` 0xc0)
+/// with a payload of `len` bytes, returning its length.
+fn append_header<S: Sink>(pack: &mut S, len: usize, offset: u8) -> usize {
+    if len <= 55 {
+        pack.put_u8(offset + len as u8);
+        1
     } else {
-        let mut len_bytes = parse_num(len.to_be_bytes()).unwrap();
-        let mut bytes = vec![0xf7 + len_bytes.len() as u8];
-        bytes.append(&mut len_bytes);
-        bytes
-    };
+        let len_bytes = parse_num(len.to_be_bytes()).expect("fine because the length exceeds 55");
+        pack.put_u8(offset + 55 + len_bytes.len() as u8);
+        pack.put_slice(&len_bytes);
+        1 + len_bytes.len()
+    }
+}
 
-    Ok(bytes)
+fn serialize_list_len<S: Sink>(pack: &mut S, len: usize) -> usize {
+    append_header(pack, len, 0xc0)
+}
+
+impl RecursiveBytes {
+    /// Length of the encoding of this item, including its prefix.
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            RecursiveBytes::Bytes(bytes) => match bytes.as_slice() {
+                [byte] if *byte <= 127 => 1,
+                bytes => header_len(bytes.len()) + bytes.len(),
+            },
+            RecursiveBytes::EmptyList => 1,
+            RecursiveBytes::Nested(recs) => {
+                let len = recs.iter().map(RecursiveBytes::encoded_len).sum();
+                header_len(len) + len
+            }
+        }
+    }
 }
 
-fn recursive_pack_rlp(pack: &mut Vec<u8>, rec: RecursiveBytes) -> Result<usize, RlpError> {
+fn recursive_pack_rlp<S: Sink>(pack: &mut S, rec: &RecursiveBytes) -> Result<usize, RlpError> {
     match rec {
         RecursiveBytes::Bytes(bytes) => append_rlp_bytes(pack, bytes),
         RecursiveBytes::EmptyList => {
-            pack.push(0x80);
+            pack.put_u8(0x80);
             Ok(1)
         }
         RecursiveBytes::Nested(recs) => {
-            let mut len = 0;
-            let inner_pack = &mut Vec::new();
+            let payload_len = recs.iter().map(RecursiveBytes::encoded_len).sum();
+            let mut len = serialize_list_len(pack, payload_len);
             for rec in recs {
-                len += recursive_pack_rlp(inner_pack, rec)?;
+                len += recursive_pack_rlp(pack, rec)?;
             }
-            let len_bytes = &mut serialize_list_len(len)?;
-            len += len_bytes.len();
-            pack.append(len_bytes);
-            pack.append(inner_pack);
             Ok(len)
         }
     }
@@ -343,7 +565,7 @@ mod tests {
         let unpacked = unpack_rlp(&dog_rlp).unwrap();
         assert_eq!(
             unpacked.0,
-            vec![RecursiveBytes::Bytes(vec![b'd', b'o', b'g'])]
+            vec![RecursiveBytes::Bytes(vec![b'd', b'o', b'g'].into())]
         );
     }
 
@@ -365,8 +587,8 @@ mod tests {
         assert_eq!(
             unpacked.0,
             vec![RecursiveBytes::Nested(vec![
-                RecursiveBytes::Bytes(vec![b'd', b'o', b'g']),
-                RecursiveBytes::Bytes(vec![b'c', b'a', b't']),
+                RecursiveBytes::Bytes(vec![b'd', b'o', b'g'].into()),
+                RecursiveBytes::Bytes(vec![b'c', b'a', b't'].into()),
             ])]
         );
     }
@@ -374,7 +596,7 @@ mod tests {
     #[test]
     fn unpack_empty_string() {
         let unpacked = unpack_rlp(&[0x80][..]).unwrap();
-        assert_eq!(unpacked.0, vec![RecursiveBytes::Bytes(Vec::new())]);
+        assert_eq!(unpacked.0, vec![RecursiveBytes::Bytes(Vec::new().into())]);
     }
 
     #[test]
@@ -386,25 +608,28 @@ mod tests {
     #[test]
     fn unpack_zero() {
         let unpacked = unpack_rlp(&[0x80][..]).unwrap();
-        assert_eq!(unpacked.0, vec![RecursiveBytes::Bytes(vec![])]);
+        assert_eq!(unpacked.0, vec![RecursiveBytes::Bytes(vec![].into())]);
     }
 
     #[test]
     fn unpack_null_byte() {
         let unpacked = unpack_rlp(&[0x00][..]).unwrap();
-        assert_eq!(unpacked.0, vec![RecursiveBytes::Bytes(vec![0])]);
+        assert_eq!(unpacked.0, vec![RecursiveBytes::Bytes(vec![0].into())]);
     }
 
     #[test]
     fn unpack_0f() {
         let unpacked = unpack_rlp(&[0x0f][..]).unwrap();
-        assert_eq!(unpacked.0, vec![RecursiveBytes::Bytes(vec![0x0f])]);
+        assert_eq!(unpacked.0, vec![RecursiveBytes::Bytes(vec![0x0f].into())]);
     }
 
     #[test]
     fn unpack_two_bytes() {
         let unpacked = unpack_rlp(&[0x82, 0x04, 0x00][..]).unwrap();
-        assert_eq!(unpacked.0, vec![RecursiveBytes::Bytes(vec![0x04, 0x00])]);
+        assert_eq!(
+            unpacked.0,
+            vec![RecursiveBytes::Bytes(vec![0x04, 0x00].into())]
+        );
     }
 
     #[test]
@@ -438,25 +663,32 @@ mod tests {
 
         assert_eq!(
             unpacked.0,
-            vec![RecursiveBytes::Bytes(vec![
-                b'L', b'o', b'r', b'e', b'm', b' ', b'i', b'p', b's', b'u', b'm', b' ', b'd', b'o',
-                b'l', b'o', b'r', b' ', b's', b'i', b't', b' ', b'a', b'm', b'e', b' ', b't', b' ',
-                b'c', b'o', b'n', b's', b'e', b'c', b't', b'e', b't', b'u', b'r', b' ', b'a', b'd',
-                b'i', b'p', b'i', b's', b'i', b'c', b'i', b'n', b'g', b' ', b'e', b'l', b'i', b't',
-            ])]
+            vec![RecursiveBytes::Bytes(
+                vec![
+                    b'L', b'o', b'r', b'e', b'm', b' ', b'i', b'p', b's', b'u', b'm', b' ', b'd',
+                    b'o', b'l', b'o', b'r', b' ', b's', b'i', b't', b' ', b'a', b'm', b'e', b' ',
+                    b't', b' ', b'c', b'o', b'n', b's', b'e', b'c', b't', b'e', b't', b'u', b'r',
+                    b' ', b'a', b'd', b'i', b'p', b'i', b's', b'i', b'c', b'i', b'n', b'g', b' ',
+                    b'e', b'l', b'i', b't',
+                ]
+                .into()
+            )]
         );
     }
 
     #[test]
-    fn trailing_bytes_unpack() {
-        let tests = [&[93, 61, 73, 95, 61, 61, 248, 0][..]];
+    fn leading_zero_length_unpack() {
+        let tests = [
+            &[93, 61, 73, 95, 61, 61, 248, 0][..],
+            &[0xb9, 0x00, 0x38][..],
+        ];
 
         for (i, bytes) in tests.into_iter().enumerate() {
             println!("{i}...");
 
             assert!(matches!(
                 unpack_rlp(bytes).unwrap_err(),
-                RlpError::TrailingBytes
+                RlpError::InvalidLength
             ));
 
             println!("ok");
@@ -489,15 +721,15 @@ mod tests {
         assert_eq!(
             rlp.0,
             vec![RecursiveBytes::Nested(vec![
-                RecursiveBytes::Bytes(vec![59]),
-                RecursiveBytes::Bytes(vec![59]),
-                RecursiveBytes::Bytes(vec![59]),
-                RecursiveBytes::Bytes(vec![59]),
-                RecursiveBytes::Bytes(vec![0]),
-                RecursiveBytes::Bytes(vec![0]),
-                RecursiveBytes::Bytes(vec![]),
-                RecursiveBytes::Bytes(vec![59]),
-                RecursiveBytes::Bytes(vec![59]),
+                RecursiveBytes::Bytes(vec![59].into()),
+                RecursiveBytes::Bytes(vec![59].into()),
+                RecursiveBytes::Bytes(vec![59].into()),
+                RecursiveBytes::Bytes(vec![59].into()),
+                RecursiveBytes::Bytes(vec![0].into()),
+                RecursiveBytes::Bytes(vec![0].into()),
+                RecursiveBytes::Bytes(vec![].into()),
+                RecursiveBytes::Bytes(vec![59].into()),
+                RecursiveBytes::Bytes(vec![59].into()),
             ])]
         );
     }
@@ -510,15 +742,15 @@ mod tests {
         assert_eq!(
             rlp.0,
             vec![RecursiveBytes::Nested(vec![
-                RecursiveBytes::Bytes(vec![]),
-                RecursiveBytes::Bytes(vec![59]),
-                RecursiveBytes::Bytes(vec![]),
-                RecursiveBytes::Bytes(vec![59]),
-                RecursiveBytes::Bytes(vec![0, 59, 59, 201]),
-                RecursiveBytes::Bytes(vec![]),
-                RecursiveBytes::Bytes(vec![59]),
-                RecursiveBytes::Bytes(vec![59]),
-                RecursiveBytes::Bytes(vec![]),
+                RecursiveBytes::Bytes(vec![].into()),
+                RecursiveBytes::Bytes(vec![59].into()),
+                RecursiveBytes::Bytes(vec![].into()),
+                RecursiveBytes::Bytes(vec![59].into()),
+                RecursiveBytes::Bytes(vec![0, 59, 59, 201].into()),
+                RecursiveBytes::Bytes(vec![].into()),
+                RecursiveBytes::Bytes(vec![59].into()),
+                RecursiveBytes::Bytes(vec![59].into()),
+                RecursiveBytes::Bytes(vec![].into()),
             ])]
         );
     }
@@ -529,29 +761,29 @@ mod tests {
             (
                 &[201, 69, 59, 59, 59, 0, 59, 59, 59, 10][..],
                 vec![RecursiveBytes::Nested(vec![
-                    RecursiveBytes::Bytes(vec![69]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![0]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![10]),
+                    RecursiveBytes::Bytes(vec![69].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![0].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![10].into()),
                 ])],
             ),
             (
                 &[201, 128, 59, 59, 59, 59, 59, 59, 59, 59][..],
                 vec![RecursiveBytes::Nested(vec![
-                    RecursiveBytes::Bytes(vec![]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![59]),
-                    RecursiveBytes::Bytes(vec![59]),
+                    RecursiveBytes::Bytes(vec![].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
+                    RecursiveBytes::Bytes(vec![59].into()),
                 ])],
             ),
         ];
@@ -587,4 +819,110 @@ mod tests {
             assert!(MyType::deserialize(rlp).is_err(),)
         }
     }
+
+    #[test]
+    fn edit_nested_path() {
+        // [[dog, cat], [], cow]
+        let bytes = [
+            0xce, 0xc8, 0x83, b'd', b'o', b'g', 0x83, b'c', b'a', b't', 0xc0, 0x83, b'c', b'o',
+            b'w',
+        ];
+        let mut rlp = unpack_rlp(&bytes).unwrap();
+
+        assert_eq!(
+            rlp.get_path(&[0, 0, 1]),
+            Some(&RecursiveBytes::Bytes(b"cat".to_vec().into()))
+        );
+        assert!(rlp.get_path(&[0, 2, 0]).is_none());
+
+        let old = rlp
+            .set(&[0, 0, 0], RecursiveBytes::Bytes(b"pig".to_vec().into()))
+            .unwrap();
+        assert_eq!(old, RecursiveBytes::Bytes(b"dog".to_vec().into()));
+
+        rlp.push(&[0, 1], RecursiveBytes::Bytes(vec![0x01].into()))
+            .unwrap();
+        rlp.insert(&[0, 0, 0], RecursiveBytes::empty_list())
+            .unwrap();
+        let removed = rlp.remove(&[0, 2]).unwrap();
+        assert_eq!(removed, RecursiveBytes::Bytes(b"cow".to_vec().into()));
+        rlp.truncate(&[0, 0], 2).unwrap();
+
+        // [[[], pig], [0x01]]
+        let packed = pack_rlp(rlp).unwrap();
+        assert_eq!(
+            packed,
+            vec![0xc8, 0xc5, 0xc0, 0x83, b'p', b'i', b'g', 0xc1, 0x01]
+        );
+    }
+
+    #[test]
+    fn edit_top_level() {
+        let mut rlp = Rlp::default();
+        rlp.push(&[], RecursiveBytes::Bytes(vec![0x01].into()))
+            .unwrap();
+        rlp.insert(&[0], RecursiveBytes::Bytes(vec![0x02].into()))
+            .unwrap();
+        rlp.insert(&[2], RecursiveBytes::Bytes(vec![0x03].into()))
+            .unwrap();
+        assert_eq!(
+            rlp.remove(&[1]).unwrap(),
+            RecursiveBytes::Bytes(vec![0x01].into())
+        );
+
+        rlp.get_nested_mut(0).unwrap_err();
+        *rlp.get_mut(1).unwrap() = RecursiveBytes::empty_list();
+        rlp.get_nested_mut(1)
+            .unwrap()
+            .push(RecursiveBytes::Bytes(vec![0x04].into()));
+
+        assert_eq!(pack_rlp(rlp.clone()).unwrap(), vec![0x02, 0xc1, 0x04]);
+
+        rlp.truncate(&[], 1).unwrap();
+        assert_eq!(pack_rlp(rlp).unwrap(), vec![0x02]);
+    }
+
+    #[test]
+    fn edit_invalid_path() {
+        let mut rlp = unpack_rlp(&[0xc2, 0x01, 0x02]).unwrap();
+
+        assert!(matches!(
+            rlp.set(&[], RecursiveBytes::Bytes(vec![].into())),
+            Err(RlpError::MissingBytes)
+        ));
+        assert!(matches!(
+            rlp.set(&[0, 2], RecursiveBytes::Bytes(vec![].into())),
+            Err(RlpError::MissingBytes)
+        ));
+        assert!(matches!(
+            rlp.push(&[0, 0], RecursiveBytes::Bytes(vec![].into())),
+            Err(RlpError::ExpectedList)
+        ));
+        assert!(matches!(
+            rlp.insert(&[0, 3], RecursiveBytes::Bytes(vec![].into())),
+            Err(RlpError::MissingBytes)
+        ));
+        assert!(matches!(
+            rlp.remove(&[0, 0, 0]),
+            Err(RlpError::ExpectedList)
+        ));
+        assert!(matches!(rlp.remove(&[1]), Err(RlpError::MissingBytes)));
+    }
+
+    #[test]
+    fn unpack_empty_input() {
+        assert!(matches!(unpack_rlp(&[]), Err(RlpError::MissingBytes)));
+    }
+
+    #[test]
+    fn unpack_long_list_non_optimal() {
+        let mut bytes = vec![0xf8, 55];
+        bytes.extend_from_slice(&[0x01; 55]);
+        assert!(matches!(unpack_rlp(&bytes), Err(RlpError::InvalidLength)));
+
+        bytes[0] = 0xf7;
+        bytes.remove(1);
+        let rlp = unpack_rlp(&bytes).unwrap();
+        assert_eq!(pack_rlp(rlp).unwrap(), bytes);
+    }
 }
@@ -0,0 +1,661 @@
+//! Decoding of `Rlp` trees with a schema parsed at runtime, for formats that are not Rust types.
+//!
+//! - `uintN` is an unsigned integer of at most `N` bits, `N` being a multiple of 8 up to 256.
+//!   `uint` is `uint256`. Integers can't have leading zeros.
+//! - `bytes` is any string, `bytesN` a string of exactly `N` bytes.
+//! - `string` is an UTF-8 string.
+//! - `bool` is `0x80` or `0x00` for false and `0x01` for true.
+//! - `list{name: schema, ...}` is a list with one named field per item.
+//! - `list[schema]` is a list of any number of items of the same schema.
+//!
+//! ```
+//! use rlp_rs::schema::{Schema, Value};
+//!
+//! let schema: Schema = "list{nonce: uint64, to: bytes20, storage_keys: list[bytes32]}"
+//!     .parse()
+//!     .unwrap();
+//!
+//! let mut bytes = vec![0xd7, 0x07, 0x94];
+//! bytes.extend_from_slice(&[0xaa; 20]);
+//! bytes.push(0xc0);
+//! let value = schema.decode_bytes(&bytes).unwrap();
+//!
+//! assert_eq!(value.get("nonce").and_then(Value::as_u64), Some(7));
+//! assert_eq!(value.get("storage_keys"), Some(&Value::List(vec![])));
+//!
+//! let error = schema.decode_bytes(&[0xc3, 0x07, 0x80, 0xc0]).unwrap_err();
+//! assert_eq!(error.to_string(), "to (bytes20): invalid length");
+//! ```
+
+use crate::{unpack_rlp, RecursiveBytes, Rlp, RlpError};
+use alloc::string::{String, ToString};
+use alloc::{boxed::Box, format, vec::Vec};
+use core::fmt::{self, Display};
+use core::str::FromStr;
+
+/// A node of a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schema {
+    /// An unsigned integer of at most this number of bits
+    Uint(usize),
+    Bytes,
+    /// Bytes of exactly this length
+    FixedBytes(usize),
+    String,
+    Bool,
+    /// A list with named fields
+    Struct(Vec<(String, Schema)>),
+    /// A list of items with the same schema
+    List(Box<Schema>),
+}
+
+/// A value decoded with a `Schema`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// Big-endian bytes without leading zeros
+    Uint(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    Bool(bool),
+    Struct(Vec<(String, Value)>),
+    List(Vec<Value>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// The schema text is invalid at this byte offset
+    Syntax {
+        offset: usize,
+        message: &'static str,
+    },
+    /// The item at `path` does not match the schema node `node`
+    Mismatch {
+        path: String,
+        node: String,
+        error: RlpError,
+    },
+}
+
+impl Schema {
+    pub fn parse(text: &str) -> Result<Self, SchemaError> {
+        let mut parser = Parser { text, offset: 0 };
+        let schema = parser.schema()?;
+        parser.skip_whitespace();
+        if parser.offset < text.len() {
+            return Err(parser.error("unexpected trailing characters"));
+        }
+        Ok(schema)
+    }
+
+    /// Decode the single item of `rlp`.
+    pub fn decode(&self, rlp: &Rlp) -> Result<Value, SchemaError> {
+        let item = match rlp.len() {
+            1 => &rlp.0[0],
+            len => {
+                let error = if len == 0 {
+                    RlpError::MissingBytes
+                } else {
+                    RlpError::TrailingBytes
+                };
+                return Err(self.mismatch("", error));
+            }
+        };
+        self.decode_item(item, &mut String::new())
+    }
+
+    pub fn decode_bytes(&self, bytes: &[u8]) -> Result<Value, SchemaError> {
+        let rlp = unpack_rlp(bytes).map_err(|error| self.mismatch("", error))?;
+        self.decode(&rlp)
+    }
+
+    fn decode_item(&self, item: &RecursiveBytes, path: &mut String) -> Result<Value, SchemaError> {
+        let value = match self {
+            Schema::Struct(fields) => {
+                let items = self.need_list(item, path)?;
+                if items.len() != fields.len() {
+                    let error = if items.len() < fields.len() {
+                        RlpError::MissingBytes
+                    } else {
+                        RlpError::InvalidLength
+                    };
+                    return Err(self.mismatch(path, error));
+                }
+
+                let mut values = Vec::with_capacity(fields.len());
+                for ((name, schema), item) in fields.iter().zip(items) {
+                    let len = path.len();
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(name);
+                    values.push((name.clone(), schema.decode_item(item, path)?));
+                    path.truncate(len);
+                }
+                Value::Struct(values)
+            }
+            Schema::List(schema) => {
+                let items = self.need_list(item, path)?;
+                let mut values = Vec::with_capacity(items.len());
+                for (index, item) in items.iter().enumerate() {
+                    let len = path.len();
+                    path.push_str(&format!("[{index}]"));
+                    values.push(schema.decode_item(item, path)?);
+                    path.truncate(len);
+                }
+                Value::List(values)
+            }
+            Schema::Uint(bits) => {
+                let bytes = self.need_bytes(item, path)?;
+                if bytes.first() == Some(&0) {
+                    return Err(self.mismatch(path, RlpError::TrailingBytes));
+                }
+                if bytes.len() * 8 > *bits {
+                    return Err(self.mismatch(path, RlpError::InvalidLength));
+                }
+                Value::Uint(bytes.to_vec())
+            }
+            Schema::Bytes => Value::Bytes(self.need_bytes(item, path)?.to_vec()),
+            Schema::FixedBytes(len) => {
+                let bytes = self.need_bytes(item, path)?;
+                if bytes.len() != *len {
+                    return Err(self.mismatch(path, RlpError::InvalidLength));
+                }
+                Value::Bytes(bytes.to_vec())
+            }
+            Schema::String => {
+                let bytes = self.need_bytes(item, path)?;
+                let string = core::str::from_utf8(bytes)
+                    .map_err(|_| self.mismatch(path, RlpError::InvalidBytes))?;
+                Value::String(string.to_string())
+            }
+            Schema::Bool => match self.need_bytes(item, path)? {
+                [] | [0] => Value::Bool(false),
+                [1] => Value::Bool(true),
+                _ => return Err(self.mismatch(path, RlpError::InvalidBytes)),
+            },
+        };
+        Ok(value)
+    }
+
+    fn need_bytes<'a>(
+        &self,
+        item: &'a RecursiveBytes,
+        path: &str,
+    ) -> Result<&'a [u8], SchemaError> {
+        match item {
+            RecursiveBytes::Bytes(bytes) => Ok(bytes),
+            RecursiveBytes::EmptyList => Ok(&[]),
+            RecursiveBytes::Nested(_) => Err(self.mismatch(path, RlpError::ExpectedBytes)),
+        }
+    }
+
+    fn need_list<'a>(
+        &self,
+        item: &'a RecursiveBytes,
+        path: &str,
+    ) -> Result<&'a [RecursiveBytes], SchemaError> {
+        match item {
+            RecursiveBytes::Nested(items) => Ok(items),
+            _ => Err(self.mismatch(path, RlpError::ExpectedList)),
+        }
+    }
+
+    fn mismatch(&self, path: &str, error: RlpError) -> SchemaError {
+        SchemaError::Mismatch {
+            path: path.to_string(),
+            node: self.to_string(),
+            error,
+        }
+    }
+}
+
+impl FromStr for Schema {
+    type Err = SchemaError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Schema::parse(text)
+    }
+}
+
+impl Display for Schema {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Schema::Uint(bits) => write!(formatter, "uint{bits}"),
+            Schema::Bytes => formatter.write_str("bytes"),
+            Schema::FixedBytes(len) => write!(formatter, "bytes{len}"),
+            Schema::String => formatter.write_str("string"),
+            Schema::Bool => formatter.write_str("bool"),
+            Schema::Struct(fields) => {
+                formatter.write_str("list{")?;
+                for (index, (name, schema)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        formatter.write_str(", ")?;
+                    }
+                    write!(formatter, "{name}: {schema}")?;
+                }
+                formatter.write_str("}")
+            }
+            Schema::List(schema) => write!(formatter, "list[{schema}]"),
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &'static str) -> SchemaError {
+        SchemaError::Syntax {
+            offset: self.offset,
+            message,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.offset..];
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    /// consume `token` if it is next.
+    fn eat(&mut self, token: char) -> bool {
+        self.skip_whitespace();
+        if self.text[self.offset..].starts_with(token) {
+            self.offset += token.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: char, message: &'static str) -> Result<(), SchemaError> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.error(message)),
+        }
+    }
+
+    fn identifier(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let rest = &self.text[self.offset..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.offset += len;
+        &rest[..len]
+    }
+
+    fn schema(&mut self) -> Result<Schema, SchemaError> {
+        self.skip_whitespace();
+        let start = self.offset;
+        let name = self.identifier();
+
+        let schema = match name {
+            "list" if self.eat('{') => self.fields()?,
+            "list" if self.eat('[') => {
+                let schema = self.schema()?;
+                self.expect(']', "expected `]`")?;
+                Schema::List(Box::new(schema))
+            }
+            "list" => return Err(self.error("expected `{` or `[`")),
+            "uint" => Schema::Uint(256),
+            "bytes" => Schema::Bytes,
+            "string" => Schema::String,
+            "bool" => Schema::Bool,
+            _ => {
+                let sized = |prefix| name.strip_prefix(prefix)?.parse::<usize>().ok();
+                match (sized("uint"), sized("bytes")) {
+                    (Some(bits), _) if bits > 0 && bits <= 256 && bits % 8 == 0 => {
+                        Schema::Uint(bits)
+                    }
+                    (_, Some(len)) if len > 0 => Schema::FixedBytes(len),
+                    _ => {
+                        self.offset = start;
+                        return Err(self.error("unknown type"));
+                    }
+                }
+            }
+        };
+        Ok(schema)
+    }
+
+    /// the fields of a struct, after its `{`.
+    fn fields(&mut self) -> Result<Schema, SchemaError> {
+        let mut fields: Vec<(String, Schema)> = Vec::new();
+        while !self.eat('}') {
+            let name = self.identifier();
+            if name.is_empty() {
+                return Err(self.error("expected a field name"));
+            }
+            if fields.iter().any(|(field, _)| field == name) {
+                self.offset -= name.len();
+                return Err(self.error("duplicate field name"));
+            }
+            self.expect(':', "expected `:`")?;
+            fields.push((name.to_string(), self.schema()?));
+
+            if !self.eat(',') {
+                self.expect('}', "expected `,` or `}`")?;
+                break;
+            }
+        }
+        Ok(Schema::Struct(fields))
+    }
+}
+
+impl Value {
+    /// The field `name` of a struct.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Struct(fields) => fields
+                .iter()
+                .find_map(|(field, value)| (field == name).then_some(value)),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Uint(bytes) if bytes.len() <= 8 => {
+                Some(bytes.iter().fold(0, |num, byte| num << 8 | *byte as u64))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Integers are printed in decimal, bytes in hex and lists between brackets.
+///
+/// ```text
+/// {nonce: 7, to: 0xaaaa, storage_keys: [0x01, 0x02]}
+/// ```
+impl Display for Value {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Uint(bytes) => formatter.write_str(&be_bytes_to_decimal(bytes)),
+            Value::Bytes(bytes) => {
+                formatter.write_str("0x")?;
+                for byte in bytes {
+                    write!(formatter, "{byte:02x}")?;
+                }
+                Ok(())
+            }
+            Value::String(string) => write!(formatter, "{string:?}"),
+            Value::Bool(bool) => write!(formatter, "{bool}"),
+            Value::Struct(fields) => {
+                formatter.write_str("{")?;
+                for (index, (name, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        formatter.write_str(", ")?;
+                    }
+                    write!(formatter, "{name}: {value}")?;
+                }
+                formatter.write_str("}")
+            }
+            Value::List(values) => {
+                formatter.write_str("[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        formatter.write_str(", ")?;
+                    }
+                    write!(formatter, "{value}")?;
+                }
+                formatter.write_str("]")
+            }
+        }
+    }
+}
+
+fn be_bytes_to_decimal(bytes: &[u8]) -> String {
+    let mut bytes = bytes.to_vec();
+    let mut digits = Vec::new();
+    while bytes.iter().any(|byte| byte > &0) {
+        let mut remainder = 0;
+        for byte in bytes.iter_mut() {
+            let val = remainder << 8 | *byte as u32;
+            *byte = (val / 10) as u8;
+            remainder = val % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    digits.reverse();
+    String::from_utf8(digits).expect("ascii digits")
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchemaError::Syntax { offset, message } => {
+                write!(formatter, "invalid schema at {offset}: {message}")
+            }
+            SchemaError::Mismatch { path, node, error } if path.is_empty() => {
+                write!(formatter, "top level ({node}): {error}")
+            }
+            SchemaError::Mismatch { path, node, error } => {
+                write!(formatter, "{path} ({node}): {error}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SchemaError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Schema, SchemaError, Value};
+    use crate::{to_bytes, RlpError};
+    use serde::Serialize;
+    use serde_bytes::ByteBuf;
+
+    const ACCESS_LIST_TX: &str = "list{
+        chain_id: uint64,
+        nonce: uint,
+        gas_price: uint256,
+        gas: uint64,
+        to: bytes20,
+        value: uint,
+        data: bytes,
+        access_list: list[list{address: bytes20, storage_keys: list[bytes32]}],
+    }";
+
+    #[derive(Serialize)]
+    struct AccessListItem {
+        #[serde(with = "serde_bytes")]
+        address: [u8; 20],
+        storage_keys: Vec<ByteBuf>,
+    }
+
+    #[derive(Serialize)]
+    struct Transaction {
+        chain_id: u64,
+        nonce: u64,
+        gas_price: u64,
+        gas: u64,
+        #[serde(with = "serde_bytes")]
+        to: [u8; 20],
+        value: u128,
+        data: ByteBuf,
+        access_list: Vec<AccessListItem>,
+    }
+
+    fn transaction() -> Transaction {
+        Transaction {
+            chain_id: 1,
+            nonce: 0,
+            gas_price: 1_000_000_000,
+            gas: 21_000,
+            to: [0xaa; 20],
+            value: 10u128.pow(30),
+            data: ByteBuf::from(vec![0xde, 0xad]),
+            access_list: vec![AccessListItem {
+                address: [0xbb; 20],
+                storage_keys: vec![ByteBuf::from(vec![0x01; 32]); 2],
+            }],
+        }
+    }
+
+    #[test]
+    fn parse_display() {
+        let tests = [
+            ("uint", "uint256"),
+            (" uint8 ", "uint8"),
+            ("bytes", "bytes"),
+            ("bytes256", "bytes256"),
+            ("list[ string ]", "list[string]"),
+            ("list{}", "list{}"),
+            (
+                "list{a:bool,b:list[uint16],}",
+                "list{a: bool, b: list[uint16]}",
+            ),
+        ];
+
+        for (i, (text, display)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            let schema = Schema::parse(text).unwrap();
+            assert_eq!(schema.to_string(), display);
+            assert_eq!(Schema::parse(display).unwrap(), schema);
+
+            println!("ok");
+        }
+
+        assert_eq!(
+            Schema::parse("list[bytes20]").unwrap(),
+            Schema::List(Box::new(Schema::FixedBytes(20)))
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let tests = [
+            ("uint7", 0, "unknown type"),
+            ("uint264", 0, "unknown type"),
+            ("bytes0", 0, "unknown type"),
+            ("", 0, "unknown type"),
+            ("list", 4, "expected `{` or `[`"),
+            ("list[uint", 9, "expected `]`"),
+            ("list{a uint}", 7, "expected `:`"),
+            ("list{a: uint b: uint}", 13, "expected `,` or `}`"),
+            ("list{a: uint, a: bool}", 14, "duplicate field name"),
+            ("list{: uint}", 5, "expected a field name"),
+            ("list{a: foo}", 8, "unknown type"),
+            ("uint bytes", 5, "unexpected trailing characters"),
+        ];
+
+        for (i, (text, offset, message)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            assert_eq!(
+                Schema::parse(text).unwrap_err(),
+                SchemaError::Syntax { offset, message }
+            );
+
+            println!("ok");
+        }
+    }
+
+    #[test]
+    fn decode_transaction() {
+        let schema: Schema = ACCESS_LIST_TX.parse().unwrap();
+        let value = schema
+            .decode_bytes(&to_bytes(&transaction()).unwrap())
+            .unwrap();
+
+        assert_eq!(value.get("gas").and_then(Value::as_u64), Some(21_000));
+        assert_eq!(
+            value.get("value").unwrap().to_string(),
+            format!("1{}", "0".repeat(30))
+        );
+        assert_eq!(value.get("data"), Some(&Value::Bytes(vec![0xde, 0xad])));
+
+        let Some(Value::List(access_list)) = value.get("access_list") else {
+            panic!("access list is a list");
+        };
+        assert_eq!(access_list.len(), 1);
+        assert_eq!(
+            access_list[0].get("storage_keys"),
+            Some(&Value::List(vec![Value::Bytes(vec![0x01; 32]); 2]))
+        );
+
+        let display = value.to_string();
+        assert!(display
+            .starts_with("{chain_id: 1, nonce: 0, gas_price: 1000000000, gas: 21000, to: 0xaaaa"));
+        assert!(display.ends_with(&format!(
+            "storage_keys: [0x{}, 0x{}]}}]}}",
+            "01".repeat(32),
+            "01".repeat(32)
+        )));
+    }
+
+    #[test]
+    fn decode_mismatch() {
+        let schema: Schema = ACCESS_LIST_TX.parse().unwrap();
+
+        let mut short_key = transaction();
+        short_key.access_list[0].storage_keys[1] = ByteBuf::from(vec![0x01; 31]);
+
+        let tests = [
+            (
+                to_bytes(&short_key).unwrap(),
+                "access_list[0].storage_keys[1]",
+                "bytes32",
+                RlpError::InvalidLength,
+            ),
+            (
+                to_bytes(&(1u8,)).unwrap(),
+                "",
+                ACCESS_LIST_TX,
+                RlpError::ExpectedList,
+            ),
+            (
+                to_bytes(&[1u8, 2]).unwrap(),
+                "",
+                ACCESS_LIST_TX,
+                RlpError::TrailingBytes,
+            ),
+            (
+                to_bytes(&vec![1u8, 2]).unwrap(),
+                "",
+                ACCESS_LIST_TX,
+                RlpError::MissingBytes,
+            ),
+        ];
+
+        for (i, (bytes, path, node, error)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            let SchemaError::Mismatch {
+                path: error_path,
+                node: error_node,
+                error: rlp_error,
+            } = schema.decode_bytes(&bytes).unwrap_err()
+            else {
+                panic!("expected a mismatch");
+            };
+            assert_eq!(error_path, path);
+            assert_eq!(error_node, Schema::parse(node).unwrap().to_string());
+            assert_eq!(rlp_error, error);
+
+            println!("ok");
+        }
+
+        let gas_price = Schema::parse("list{gas_price: uint32}").unwrap();
+        let error = gas_price
+            .decode_bytes(&to_bytes(&vec![u64::MAX]).unwrap())
+            .unwrap_err();
+        assert_eq!(error.to_string(), "gas_price (uint32): invalid length");
+
+        let uint = Schema::parse("uint").unwrap();
+        assert_eq!(
+            uint.decode_bytes(&[0x82, 0x00, 0x01])
+                .unwrap_err()
+                .to_string(),
+            "top level (uint256): trailing bytes"
+        );
+    }
+}
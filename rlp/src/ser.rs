@@ -1,9 +1,12 @@
-use crate::{pack_rlp, RecursiveBytes, Rlp, RlpError};
+use crate::layout::{AS_LIST, INLINE};
+use crate::{header_len, pack_rlp, RecursiveBytes, Rlp, RlpError, SizeReport};
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::{format, vec, vec::Vec};
+use core::cell::RefCell;
 use paste::paste;
 use serde::{ser, Serialize};
-use std::cell::RefCell;
-use std::collections::VecDeque;
-use std::rc::Rc;
 
 #[derive(Debug)]
 enum RefRecursiveBytes {
@@ -12,15 +15,50 @@ enum RefRecursiveBytes {
     Nested(Rc<RefCell<Vec<RefRecursiveBytes>>>),
 }
 
+type RefList = Rc<RefCell<Vec<RefRecursiveBytes>>>;
+
+/// A field being serialized for a size report, with the list it is serialized in
+/// (or `None` for the highest level), the path of that list and the index of its first item in there.
+struct ReportedField {
+    list: Option<RefList>,
+    list_path: Vec<usize>,
+    start: usize,
+    report: SizeReport,
+}
+
 #[derive(Default)]
 struct Serializer {
     output: VecDeque<RefRecursiveBytes>,
-    stack: Vec<Rc<RefCell<Vec<RefRecursiveBytes>>>>,
+    stack: Vec<RefList>,
+    /// only used by `size_report`, the fields that are currently being serialized
+    fields: Option<Vec<ReportedField>>,
+    /// only used when reporting fields, the path of the most nested list we are in
+    list_path: Vec<usize>,
+    /// only used by `field_labels`, the field path of the items by item path
+    labels: Option<BTreeMap<Vec<usize>, String>>,
+    /// the next list should not be opened, its items go to the enclosing list (`Inline`)
+    inline_next: bool,
+    /// whether each list we are in was actually opened or inlined
+    opened: Vec<bool>,
 }
 
 impl Serializer {
     /// pushes a new list to the most nested one we are currently in
     fn new_list(&mut self) {
+        let inline = core::mem::take(&mut self.inline_next);
+        self.opened.push(!inline);
+        if inline {
+            return;
+        }
+
+        if self.fields.is_some() {
+            let index = match self.stack.last() {
+                Some(top) => top.borrow().len(),
+                None => self.output.len(),
+            };
+            self.list_path.push(index);
+        }
+
         let rc_list = Rc::new(RefCell::new(Vec::with_capacity(0)));
         let nested = RefRecursiveBytes::Nested(rc_list.clone());
 
@@ -35,11 +73,22 @@ impl Serializer {
 
     /// forget about the reference to the nested list and go one level higher.
     fn end_list(&mut self) {
+        if !self.opened.pop().unwrap_or(true) {
+            return;
+        }
+
+        if self.fields.is_some() {
+            self.list_path.pop();
+        }
         self.stack.pop();
     }
 
     /// pushes bytes to the most nested list we are in or at the highest level.
-    fn push_bytes(&mut self, bytes: &[u8], fixed: bool) {
+    fn push_bytes(&mut self, bytes: &[u8], fixed: bool) -> Result<(), RlpError> {
+        if self.inline_next {
+            return Err(inline_error());
+        }
+
         let bytes = if fixed {
             if let Some(index) = bytes.iter().position(|b| b > &0) {
                 RefRecursiveBytes::Data(bytes[index..].to_vec())
@@ -57,11 +106,117 @@ impl Serializer {
         } else {
             self.output.push_back(bytes);
         }
+        Ok(())
+    }
+
+    /// start recording the size of a field, if building a size report.
+    /// An unnamed field is named after its index in the enclosing value.
+    fn enter_field(&mut self, name: Option<&'static str>) {
+        let Some(fields) = &self.fields else {
+            return;
+        };
+
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => {
+                let index = fields.last().map_or(0, |field| field.report.children.len());
+                format!("[{index}]")
+            }
+        };
+        let list = self.stack.last().cloned();
+        let start = match &list {
+            Some(list) => list.borrow().len(),
+            None => self.output.len(),
+        };
+
+        if let Some(fields) = &mut self.fields {
+            fields.push(ReportedField {
+                list,
+                list_path: self.list_path.clone(),
+                start,
+                report: SizeReport {
+                    name,
+                    ..Default::default()
+                },
+            });
+        }
+    }
+
+    /// stop recording the size of the last entered field, and attach it to its parent.
+    fn exit_field(&mut self) {
+        let Some(fields) = &mut self.fields else {
+            return;
+        };
+        let Some(mut field) = fields.pop() else {
+            return;
+        };
+
+        let (prefix, payload) = match &field.list {
+            Some(list) => Self::encoded_sizes(&list.borrow()[field.start..]),
+            None => Self::encoded_sizes(self.output.range(field.start..)),
+        };
+        field.report.prefix = prefix;
+        field.report.payload = payload;
+
+        if let Some(labels) = &mut self.labels {
+            let end = match &field.list {
+                Some(list) => list.borrow().len(),
+                None => self.output.len(),
+            };
+            let mut label = String::new();
+            for name in fields
+                .iter()
+                .chain([&field])
+                .map(|field| &field.report.name)
+            {
+                if !label.is_empty() && !name.starts_with('[') {
+                    label.push('.');
+                }
+                label.push_str(name);
+            }
+
+            // the items of a nested field were already labeled after it
+            for index in (field.start..end).filter(|_| !label.is_empty()) {
+                let mut path = field.list_path.clone();
+                path.push(index);
+                labels.entry(path).or_insert_with(|| label.clone());
+            }
+        }
+
+        if let Some(parent) = fields.last_mut() {
+            parent.report.children.push(field.report);
+        }
+    }
+
+    /// name the value of the field being recorded after its type, if it has none yet.
+    fn name_type(&mut self, name: &'static str) {
+        if let Some(field) = self.fields.as_mut().and_then(|fields| fields.last_mut()) {
+            field.report.type_name.get_or_insert(name);
+        }
+    }
+
+    /// the sum of the prefix and payload lengths of some encoded items.
+    fn encoded_sizes<'a>(recs: impl IntoIterator<Item = &'a RefRecursiveBytes>) -> (usize, usize) {
+        recs.into_iter()
+            .fold((0, 0), |(prefix, payload), rec| match rec {
+                RefRecursiveBytes::Data(bytes) if bytes.len() == 1 && bytes[0] <= 0x7f => {
+                    (prefix, payload + 1)
+                }
+                RefRecursiveBytes::Data(bytes) => {
+                    (prefix + header_len(bytes.len()), payload + bytes.len())
+                }
+                RefRecursiveBytes::EmptyList => (prefix + 1, payload),
+                RefRecursiveBytes::Nested(list) => {
+                    let (inner_prefix, inner_payload) = Self::encoded_sizes(list.borrow().iter());
+                    let len = inner_prefix + inner_payload;
+                    (prefix + header_len(len), payload + len)
+                }
+            })
     }
 
     fn recursive_into_recursive_bytes(rec: RefRecursiveBytes) -> RecursiveBytes {
         match rec {
-            RefRecursiveBytes::Data(bytes) => RecursiveBytes::Bytes(bytes),
+            RefRecursiveBytes::Data(bytes) => RecursiveBytes::Bytes(bytes.into()),
             RefRecursiveBytes::EmptyList => RecursiveBytes::EmptyList,
             RefRecursiveBytes::Nested(list) => {
                 let list = Rc::try_unwrap(list).unwrap().into_inner();
@@ -96,6 +251,43 @@ where
     Ok(serializer.into_rlp())
 }
 
+pub(crate) fn report_sizes<T>(value: &T) -> Result<SizeReport, RlpError>
+where
+    T: ?Sized + Serialize,
+{
+    let mut serializer = Serializer {
+        fields: Some(Vec::new()),
+        ..Default::default()
+    };
+    serializer.enter_field(Some(""));
+    value.serialize(&mut serializer)?;
+
+    let fields = serializer.fields.as_mut().expect("reporting sizes");
+    let mut root = fields.pop().expect("root was entered first");
+    assert!(fields.is_empty(), "all the fields have been exited");
+    let (prefix, payload) = Serializer::encoded_sizes(serializer.output.iter());
+    root.report.prefix = prefix;
+    root.report.payload = payload;
+    Ok(root.report)
+}
+
+/// The field path of each item that `value` is serialized to, by item path.
+pub(crate) fn field_labels<T>(value: &T) -> Result<BTreeMap<Vec<usize>, String>, RlpError>
+where
+    T: ?Sized + Serialize,
+{
+    let mut serializer = Serializer {
+        fields: Some(Vec::new()),
+        labels: Some(BTreeMap::new()),
+        ..Default::default()
+    };
+    serializer.enter_field(Some(""));
+    value.serialize(&mut serializer)?;
+    serializer.exit_field();
+
+    Ok(serializer.labels.unwrap_or_default())
+}
+
 pub fn to_bytes<T>(value: &T) -> Result<Vec<u8>, RlpError>
 where
     T: Serialize,
@@ -114,14 +306,38 @@ macro_rules! impl_int {
     };
 }
 
+/// Signed integers follow the Ethereum convention: non-negative values are encoded
+/// like unsigned ones and negative values are rejected.
+/// See the `signed` module for encodings supporting negative values.
+macro_rules! impl_signed {
+    ($ty:ty) => {
+        paste! {
+            fn [< serialize_ $ty >](self, v: $ty) -> Result<Self::Ok, Self::Error> {
+                if v < 0 {
+                    return Err(ser::Error::custom(concat!(
+                        "negative ",
+                        stringify!($ty),
+                        ", use one of the `signed` encodings"
+                    )));
+                }
+                self.serialize_array(v.to_be_bytes())
+            }
+        }
+    };
+}
+
+/// `Inline` around a value that is not a list, such as an integer or an enum.
+fn inline_error() -> RlpError {
+    ser::Error::custom("`Inline` only applies to structs, tuples and sequences")
+}
+
 impl Serializer {
     fn serialize_array<const N: usize>(&mut self, bytes: [u8; N]) -> Result<(), RlpError> {
-        self.push_bytes(&bytes, true);
-        Ok(())
+        self.push_bytes(&bytes, true)
     }
 }
 
-impl<'a> ser::Serializer for &'a mut Serializer {
+impl ser::Serializer for &mut Serializer {
     type Ok = ();
 
     type Error = RlpError; // TODO change the name of this error
@@ -138,15 +354,17 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         self.serialize_array(if v { [1] } else { [0] })
     }
 
-    impl_int!(i8);
-    impl_int!(i16);
-    impl_int!(i32);
-    impl_int!(i64);
+    impl_signed!(i8);
+    impl_signed!(i16);
+    impl_signed!(i32);
+    impl_signed!(i64);
+    impl_signed!(i128);
 
     impl_int!(u8);
     impl_int!(u16);
     impl_int!(u32);
     impl_int!(u64);
+    impl_int!(u128);
 
     fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
         unimplemented!()
@@ -157,7 +375,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     }
 
     fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
-        self.serialize_array([v as u8])
+        self.serialize_array((v as u32).to_be_bytes())
     }
 
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
@@ -165,8 +383,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        self.push_bytes(v, false);
-        Ok(())
+        self.push_bytes(v, false)
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
@@ -184,7 +401,8 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         self.serialize_bytes(&[])
     }
 
-    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.name_type(name);
         self.serialize_unit()
     }
 
@@ -202,13 +420,33 @@ impl<'a> ser::Serializer for &'a mut Serializer {
 
     fn serialize_newtype_struct<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(self)
+        match name {
+            AS_LIST => {
+                self.new_list();
+                value.serialize(&mut *self)?;
+                self.end_list();
+                Ok(())
+            }
+            INLINE => {
+                self.inline_next = true;
+                value.serialize(&mut *self)?;
+                // not taken by a list or a tuple
+                match core::mem::take(&mut self.inline_next) {
+                    true => Err(inline_error()),
+                    false => Ok(()),
+                }
+            }
+            _ => {
+                self.name_type(name);
+                value.serialize(self)
+            }
+        }
     }
 
     fn serialize_newtype_variant<T>(
@@ -233,14 +471,17 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        // already inlined, `Inline` doesn't apply to the elements
+        self.inline_next = false;
         Ok(self)
     }
 
     fn serialize_tuple_struct(
         self,
-        _name: &'static str,
+        name: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.name_type(name);
         self.serialize_seq(Some(len))
     }
 
@@ -263,27 +504,26 @@ impl<'a> ser::Serializer for &'a mut Serializer {
 
     fn serialize_struct(
         self,
-        _name: &'static str,
+        name: &'static str,
         len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.name_type(name);
         self.serialize_seq(Some(len))
     }
 
     fn serialize_struct_variant(
         self,
-        _name: &'static str,
-        _variant_index: u32,
+        name: &'static str,
+        variant_index: u32,
         variant: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        if !variant.is_empty() {
-            self.serialize_str(variant)?;
-        }
-        Ok(self)
+        // like tuple variants, the fields are a list following the name of the variant
+        self.serialize_tuple_variant(name, variant_index, variant, len)
     }
 }
 
-impl<'a> ser::SerializeSeq for &'a mut Serializer {
+impl ser::SerializeSeq for &mut Serializer {
     type Ok = ();
 
     type Error = RlpError;
@@ -292,7 +532,10 @@ impl<'a> ser::SerializeSeq for &'a mut Serializer {
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(&mut **self)
+        self.enter_field(None);
+        value.serialize(&mut **self)?;
+        self.exit_field();
+        Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -301,7 +544,7 @@ impl<'a> ser::SerializeSeq for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeTuple for &'a mut Serializer {
+impl ser::SerializeTuple for &mut Serializer {
     type Ok = ();
 
     type Error = RlpError;
@@ -310,7 +553,10 @@ impl<'a> ser::SerializeTuple for &'a mut Serializer {
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(&mut **self)
+        self.enter_field(None);
+        value.serialize(&mut **self)?;
+        self.exit_field();
+        Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -319,7 +565,7 @@ impl<'a> ser::SerializeTuple for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
+impl ser::SerializeTupleStruct for &mut Serializer {
     type Ok = ();
 
     type Error = RlpError;
@@ -328,7 +574,10 @@ impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(&mut **self)
+        self.enter_field(None);
+        value.serialize(&mut **self)?;
+        self.exit_field();
+        Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -337,7 +586,7 @@ impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
+impl ser::SerializeTupleVariant for &mut Serializer {
     type Ok = ();
 
     type Error = RlpError;
@@ -346,7 +595,10 @@ impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(&mut **self)
+        self.enter_field(None);
+        value.serialize(&mut **self)?;
+        self.exit_field();
+        Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -355,7 +607,7 @@ impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeMap for &'a mut Serializer {
+impl ser::SerializeMap for &mut Serializer {
     type Ok = ();
 
     type Error = RlpError;
@@ -379,16 +631,19 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeStruct for &'a mut Serializer {
+impl ser::SerializeStruct for &mut Serializer {
     type Ok = ();
 
     type Error = RlpError;
 
-    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(&mut **self)
+        self.enter_field(Some(key));
+        value.serialize(&mut **self)?;
+        self.exit_field();
+        Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -397,16 +652,19 @@ impl<'a> ser::SerializeStruct for &'a mut Serializer {
     }
 }
 
-impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
+impl ser::SerializeStructVariant for &mut Serializer {
     type Ok = ();
 
     type Error = RlpError;
 
-    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error>
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(&mut **self)
+        self.enter_field(Some(key));
+        value.serialize(&mut **self)?;
+        self.exit_field();
+        Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
@@ -419,7 +677,7 @@ impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
 mod tests {
     use super::to_bytes;
     use crate::ser::to_rlp;
-    use crate::{pack_rlp, RecursiveBytes, Rlp};
+    use crate::{pack_rlp, RecursiveBytes, Rlp, RlpError};
     use serde::Serialize;
 
     #[test]
@@ -429,8 +687,18 @@ mod tests {
         assert_eq!(serialized, vec![0x7f]);
 
         let num = -127i8;
-        let serialized = to_bytes(&num).unwrap();
-        assert_eq!(serialized, vec![0x81, (-127i8).to_be_bytes()[0]]);
+        assert!(matches!(to_bytes(&num), Err(RlpError::Message(_))));
+    }
+
+    #[test]
+    fn ser_128() {
+        let serialized = to_bytes(&(u128::MAX, i128::MAX, 1024i128)).unwrap();
+        let mut bytes = vec![0x80 + 16];
+        bytes.extend_from_slice(&u128::MAX.to_be_bytes());
+        bytes.push(0x80 + 16);
+        bytes.extend_from_slice(&i128::MAX.to_be_bytes());
+        bytes.extend_from_slice(&[0x82, 0x04, 0x00]);
+        assert_eq!(serialized, bytes);
     }
 
     #[test]
@@ -438,7 +706,7 @@ mod tests {
         let ch = 'A';
 
         let rlp = to_rlp(&ch).unwrap();
-        assert_eq!(rlp.0, vec![RecursiveBytes::Bytes(vec![0x41])]);
+        assert_eq!(rlp.0, vec![RecursiveBytes::Bytes(vec![0x41].into())]);
 
         let serialized = to_bytes(&ch).unwrap();
         assert_eq!(serialized, vec![0x41])
@@ -459,8 +727,8 @@ mod tests {
         let dog = String::from("dog");
 
         let rlp = Rlp::new_unary(RecursiveBytes::Nested(vec![
-            RecursiveBytes::Bytes(cat.as_bytes().to_vec()),
-            RecursiveBytes::Bytes(dog.as_bytes().to_vec()),
+            RecursiveBytes::Bytes(cat.as_bytes().to_vec().into()),
+            RecursiveBytes::Bytes(dog.as_bytes().to_vec().into()),
         ]));
 
         let packed = pack_rlp(rlp).unwrap();
@@ -483,8 +751,8 @@ mod tests {
         let vec = vec![cat.clone(), dog.clone()];
 
         let expected_rlp = Rlp::new_unary(RecursiveBytes::Nested(vec![
-            RecursiveBytes::Bytes(cat.as_bytes().to_vec()),
-            RecursiveBytes::Bytes(dog.as_bytes().to_vec()),
+            RecursiveBytes::Bytes(cat.as_bytes().to_vec().into()),
+            RecursiveBytes::Bytes(dog.as_bytes().to_vec().into()),
         ]));
 
         let rlp = to_rlp(&vec).unwrap();
@@ -500,11 +768,11 @@ mod tests {
         assert_eq!(
             rlp.0,
             vec![RecursiveBytes::Nested(vec![
-                RecursiveBytes::Bytes(vec![1]),
-                RecursiveBytes::Bytes(vec![2]),
-                RecursiveBytes::Bytes(vec![3]),
-                RecursiveBytes::Bytes(vec![4]),
-                RecursiveBytes::Bytes(vec![5]),
+                RecursiveBytes::Bytes(vec![1].into()),
+                RecursiveBytes::Bytes(vec![2].into()),
+                RecursiveBytes::Bytes(vec![3].into()),
+                RecursiveBytes::Bytes(vec![4].into()),
+                RecursiveBytes::Bytes(vec![5].into()),
             ])]
         );
 
@@ -524,9 +792,9 @@ mod tests {
     #[derive(Debug, PartialEq, Serialize)]
     enum Message {
         Quit,
-        Move { x: i32, y: i32 },
+        Move { x: u32, y: u32 },
         Write(String),
-        ChangeColor(i32, i32, i32),
+        ChangeColor(u32, u32, u32),
     }
 
     #[test]
@@ -549,33 +817,35 @@ mod tests {
 
     #[test]
     fn ser_enum_tuple() {
-        let serialized = to_bytes(&Message::ChangeColor(i32::MAX, -1, i32::MIN)).unwrap();
+        let serialized = to_bytes(&Message::ChangeColor(u32::MAX, 0xdeadbeef, 1 << 31)).unwrap();
         let mut bytes = vec![0x80 + "ChangeColor".len() as u8];
         bytes.extend_from_slice("ChangeColor".as_bytes());
-        bytes.push(0xc0 + (i32::BITS as u8 / 8 + 1) * 3);
-        bytes.push(0x80 + i32::BITS as u8 / 8);
-        bytes.extend_from_slice(&i32::MAX.to_be_bytes());
-        bytes.push(0x80 + i32::BITS as u8 / 8);
-        bytes.extend_from_slice(&(-1i32).to_be_bytes());
-        bytes.push(0x80 + i32::BITS as u8 / 8);
-        bytes.extend_from_slice(&i32::MIN.to_be_bytes());
+        bytes.push(0xc0 + (u32::BITS as u8 / 8 + 1) * 3);
+        bytes.push(0x80 + u32::BITS as u8 / 8);
+        bytes.extend_from_slice(&u32::MAX.to_be_bytes());
+        bytes.push(0x80 + u32::BITS as u8 / 8);
+        bytes.extend_from_slice(&0xdeadbeefu32.to_be_bytes());
+        bytes.push(0x80 + u32::BITS as u8 / 8);
+        bytes.extend_from_slice(&(1u32 << 31).to_be_bytes());
         assert_eq!(serialized, bytes);
     }
 
     #[test]
     fn ser_enum_struct() {
         let message = Message::Move {
-            x: i32::MAX,
-            y: -10,
+            x: u32::MAX,
+            y: 0xdeadbeef,
         };
 
         let rlp = to_rlp(&message).unwrap();
         assert_eq!(
             rlp.0,
             vec![
-                RecursiveBytes::Bytes("Move".as_bytes().to_vec()),
-                RecursiveBytes::Bytes(i32::MAX.to_be_bytes().to_vec()),
-                RecursiveBytes::Bytes((-10i32).to_be_bytes().to_vec()),
+                RecursiveBytes::Bytes("Move".as_bytes().to_vec().into()),
+                RecursiveBytes::Nested(vec![
+                    RecursiveBytes::Bytes(u32::MAX.to_be_bytes().to_vec().into()),
+                    RecursiveBytes::Bytes(0xdeadbeefu32.to_be_bytes().to_vec().into()),
+                ]),
             ]
         );
 
@@ -583,10 +853,11 @@ mod tests {
 
         let mut bytes = vec![0x80 + "Move".len() as u8];
         bytes.extend_from_slice("Move".as_bytes());
-        bytes.push(0x80 + i32::BITS as u8 / 8);
-        bytes.extend_from_slice(&i32::MAX.to_be_bytes());
-        bytes.push(0x80 + i32::BITS as u8 / 8);
-        bytes.extend_from_slice(&(-10i32).to_be_bytes());
+        bytes.push(0xc0 + (u32::BITS as u8 / 8 + 1) * 2);
+        bytes.push(0x80 + u32::BITS as u8 / 8);
+        bytes.extend_from_slice(&u32::MAX.to_be_bytes());
+        bytes.push(0x80 + u32::BITS as u8 / 8);
+        bytes.extend_from_slice(&0xdeadbeefu32.to_be_bytes());
         assert_eq!(serialized, bytes);
     }
 
@@ -605,9 +876,9 @@ mod tests {
         assert_eq!(
             rlp.0,
             vec![RecursiveBytes::Nested(vec![
-                RecursiveBytes::Bytes(vec![1; 10]),
-                RecursiveBytes::Bytes(vec![1; 20]),
-                RecursiveBytes::Bytes(vec![1; 30])
+                RecursiveBytes::Bytes(vec![1; 10].into()),
+                RecursiveBytes::Bytes(vec![1; 20].into()),
+                RecursiveBytes::Bytes(vec![1; 30].into())
             ])]
         );
     }
@@ -654,8 +925,8 @@ mod tests {
         assert_eq!(
             rlp.0,
             vec![
-                RecursiveBytes::Bytes("Variant1".as_bytes().to_vec()),
-                RecursiveBytes::EmptyList
+                RecursiveBytes::Bytes("Variant1".as_bytes().to_vec().into()),
+                RecursiveBytes::Nested(vec![RecursiveBytes::EmptyList])
             ]
         );
     }
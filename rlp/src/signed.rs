@@ -0,0 +1,263 @@
+//! Encodings for signed integers that support negative values.
+//!
+//! By default, signed integers (`i8` to `i128`) follow the Ethereum convention:
+//! non-negative values are encoded exactly like unsigned integers, and negative values
+//! are rejected, both when serializing and deserializing.
+//! To encode negative values, annotate the field with one of the modules below:
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct Point {
+//!     #[serde(with = "rlp_rs::signed::twos_complement")]
+//!     x: i64,
+//!     #[serde(with = "rlp_rs::signed::zigzag")]
+//!     y: i32,
+//! }
+//! ```
+//!
+//! Both encodings are canonical: zero is the empty string, and decoding rejects
+//! values that are not encoded on the minimal number of bytes.
+
+use alloc::vec::Vec;
+
+/// Signed integers supported by the encodings of this module.
+pub trait SignedInt: Copy {
+    const BYTES: usize;
+
+    fn to_i128(self) -> i128;
+
+    fn from_i128(value: i128) -> Option<Self>;
+}
+
+macro_rules! impl_signed_int {
+    ($($ty:ty),*) => {
+        $(
+            impl SignedInt for $ty {
+                const BYTES: usize = core::mem::size_of::<$ty>();
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Option<Self> {
+                    <$ty>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_signed_int!(i8, i16, i32, i64, i128);
+
+/// Two's complement on the minimal number of bytes that keeps the sign,
+/// so `-1` is `0xff`, `127` is `0x7f` and `128` is `0x0080`.
+pub mod twos_complement {
+    use super::SignedInt;
+    use alloc::vec::Vec;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use serde_bytes::ByteBuf;
+
+    pub fn encode<T: SignedInt>(value: T) -> Vec<u8> {
+        let bytes = value.to_i128().to_be_bytes();
+        let mut index = 0;
+        while index < bytes.len() - 1 {
+            let redundant = match bytes[index] {
+                0x00 => bytes[index + 1] < 0x80,
+                0xff => bytes[index + 1] >= 0x80,
+                _ => false,
+            };
+            if !redundant {
+                break;
+            }
+            index += 1;
+        }
+
+        if bytes[index..] == [0] {
+            Vec::new()
+        } else {
+            bytes[index..].to_vec()
+        }
+    }
+
+    pub fn decode<T: SignedInt>(bytes: &[u8]) -> Option<T> {
+        match bytes {
+            [] => return T::from_i128(0),
+            [0x00] | [0x00, 0x00..=0x7f, ..] | [0xff, 0x80..=0xff, ..] => return None,
+            _ if bytes.len() > T::BYTES => return None,
+            _ => {}
+        }
+
+        let fill = if bytes[0] & 0x80 != 0 { 0xff } else { 0x00 };
+        let mut full = [fill; 16];
+        full[(16 - bytes.len())..].copy_from_slice(bytes);
+        T::from_i128(i128::from_be_bytes(full))
+    }
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: SignedInt,
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&encode(*value))
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: SignedInt,
+        D: Deserializer<'de>,
+    {
+        let bytes = ByteBuf::deserialize(deserializer)?;
+        decode(&bytes).ok_or_else(|| {
+            de::Error::invalid_value(
+                de::Unexpected::Bytes(&bytes),
+                &"a minimal two's complement integer",
+            )
+        })
+    }
+}
+
+/// Zigzag mapping to an unsigned integer, encoded like any unsigned integer,
+/// so `0` is the empty string, `-1` is `0x01`, `1` is `0x02` and `-2` is `0x03`.
+pub mod zigzag {
+    use super::{strip_leading_zeros, SignedInt};
+    use alloc::vec::Vec;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use serde_bytes::ByteBuf;
+
+    pub fn encode<T: SignedInt>(value: T) -> Vec<u8> {
+        let value = value.to_i128();
+        let zigzag = ((value << 1) ^ (value >> 127)) as u128;
+        strip_leading_zeros(&zigzag.to_be_bytes())
+    }
+
+    pub fn decode<T: SignedInt>(bytes: &[u8]) -> Option<T> {
+        if bytes.len() > T::BYTES || bytes.first() == Some(&0) {
+            return None;
+        }
+
+        let mut full = [0; 16];
+        full[(16 - bytes.len())..].copy_from_slice(bytes);
+        let zigzag = u128::from_be_bytes(full);
+        T::from_i128((zigzag >> 1) as i128 ^ -((zigzag & 1) as i128))
+    }
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: SignedInt,
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&encode(*value))
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: SignedInt,
+        D: Deserializer<'de>,
+    {
+        let bytes = ByteBuf::deserialize(deserializer)?;
+        decode(&bytes).ok_or_else(|| {
+            de::Error::invalid_value(de::Unexpected::Bytes(&bytes), &"a minimal zigzag integer")
+        })
+    }
+}
+
+fn strip_leading_zeros(bytes: &[u8]) -> Vec<u8> {
+    let index = bytes.iter().position(|b| b > &0).unwrap_or(bytes.len());
+    bytes[index..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{twos_complement, zigzag};
+    use crate::{from_bytes, to_bytes, RlpError};
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn twos_complement_bytes() {
+        #[rustfmt::skip]
+        let tests: [(i64, &[u8]); 9] = [
+            (0, &[]),
+            (1, &[0x01]),
+            (127, &[0x7f]),
+            (128, &[0x00, 0x80]),
+            (-1, &[0xff]),
+            (-128, &[0x80]),
+            (-129, &[0xff, 0x7f]),
+            (i64::MAX, &[0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            (i64::MIN, &[0x80, 0, 0, 0, 0, 0, 0, 0]),
+        ];
+
+        for (i, (num, bytes)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            assert_eq!(twos_complement::encode(num), bytes);
+            assert_eq!(twos_complement::decode::<i64>(bytes), Some(num));
+
+            println!("ok");
+        }
+
+        assert_eq!(twos_complement::decode::<i8>(&[0x00]), None);
+        assert_eq!(twos_complement::decode::<i8>(&[0x00, 0x7f]), None);
+        assert_eq!(twos_complement::decode::<i8>(&[0xff, 0xff]), None);
+        assert_eq!(twos_complement::decode::<i8>(&[0x00, 0x80]), None);
+        assert_eq!(twos_complement::decode::<i16>(&[0x00, 0x80]), Some(128));
+    }
+
+    #[test]
+    fn zigzag_bytes() {
+        #[rustfmt::skip]
+        let tests: [(i32, &[u8]); 7] = [
+            (0, &[]),
+            (-1, &[0x01]),
+            (1, &[0x02]),
+            (-2, &[0x03]),
+            (-64, &[0x7f]),
+            (64, &[0x80]),
+            (i32::MIN, &[0xff, 0xff, 0xff, 0xff]),
+        ];
+
+        for (i, (num, bytes)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            assert_eq!(zigzag::encode(num), bytes);
+            assert_eq!(zigzag::decode::<i32>(bytes), Some(num));
+
+            println!("ok");
+        }
+
+        assert_eq!(zigzag::decode::<i8>(&[0x00, 0x01]), None);
+        assert_eq!(zigzag::decode::<i8>(&[0x01, 0x00]), None);
+        assert_eq!(zigzag::decode::<i128>(&[0xff; 16]), Some(i128::MIN));
+    }
+
+    #[test]
+    fn signed_policies() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Point {
+            #[serde(with = "twos_complement")]
+            x: i64,
+            #[serde(with = "zigzag")]
+            y: i16,
+            z: i32,
+        }
+
+        let point = Point {
+            x: -1,
+            y: -2,
+            z: 1024,
+        };
+        let bytes = to_bytes(&point).unwrap();
+        assert_eq!(bytes, [0xc6, 0x81, 0xff, 0x03, 0x82, 0x04, 0x00]);
+        assert_eq!(from_bytes::<Point>(&bytes).unwrap(), point);
+
+        assert!(matches!(
+            from_bytes::<Point>(&[0xc7, 0x82, 0xff, 0xff, 0x03, 0x82, 0x04, 0x00]),
+            Err(RlpError::Message(_))
+        ));
+        assert!(matches!(
+            to_bytes(&Point { z: -1, ..point }),
+            Err(RlpError::Message(_))
+        ));
+    }
+}
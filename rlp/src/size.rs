@@ -0,0 +1,261 @@
+use crate::{ser::report_sizes, RlpError};
+use alloc::string::String;
+use alloc::{vec, vec::Vec};
+use core::fmt::{self, Display};
+use serde::Serialize;
+
+/// How many bytes a value and each of its fields contribute to its encoding.
+/// Fields are named after the serde field names, or after their index in sequences and tuples.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SizeReport {
+    /// Name of the field, empty for the reported value itself
+    pub name: String,
+    /// Name of the type of the field, when serde provides one (structs)
+    pub type_name: Option<&'static str>,
+    /// Bytes of the RLP prefixes of the field
+    pub prefix: usize,
+    /// Bytes of the field after its prefixes
+    pub payload: usize,
+    pub children: Vec<SizeReport>,
+}
+
+/// Build the size report of a value, as it would be encoded by `to_bytes`.
+///
+/// ```
+/// # use serde::Serialize;
+/// #[derive(Serialize)]
+/// struct Dog {
+///     name: String,
+///     tricks: Vec<String>,
+/// }
+///
+/// let dog = Dog {
+///     name: String::from("doggo"),
+///     tricks: vec![String::from("sit"), String::from("roll")],
+/// };
+/// let report = rlp_rs::size_report(&dog).unwrap();
+///
+/// assert_eq!(report.total(), rlp_rs::to_bytes(&dog).unwrap().len());
+/// assert_eq!(report.get("tricks[1]").unwrap().payload, 4);
+/// ```
+pub fn size_report<T>(value: &T) -> Result<SizeReport, RlpError>
+where
+    T: ?Sized + Serialize,
+{
+    report_sizes(value)
+}
+
+impl SizeReport {
+    pub fn total(&self) -> usize {
+        self.prefix + self.payload
+    }
+
+    /// Look up a field by its path, such as `header.bloom` or `transactions[3].data`.
+    /// The empty path is the reported value itself.
+    pub fn get(&self, path: &str) -> Option<&SizeReport> {
+        self.paths()
+            .into_iter()
+            .find_map(|(field_path, report)| (field_path == path).then_some(report))
+    }
+
+    /// All the fields with their path, parents first.
+    pub fn paths(&self) -> Vec<(String, &SizeReport)> {
+        let mut paths = vec![(String::new(), self)];
+        self.collect_paths(String::new(), &mut paths);
+        paths
+    }
+
+    fn collect_paths<'a>(&'a self, path: String, paths: &mut Vec<(String, &'a SizeReport)>) {
+        for child in self.children.iter() {
+            let mut child_path = path.clone();
+            if !child_path.is_empty() && !child.name.starts_with('[') {
+                child_path.push('.');
+            }
+            child_path.push_str(&child.name);
+            paths.push((child_path.clone(), child));
+            child.collect_paths(child_path, paths);
+        }
+    }
+}
+
+/// One line per field, with its total size, its prefix and payload sizes
+/// and its share of the total size of the reported value.
+///
+/// ```text
+///  total prefix payload  share  path
+///     17      1      16 100.0%  (Dog)
+///      6      1       5  35.3%  name
+///     10      1       9  58.8%  tricks
+/// ```
+impl Display for SizeReport {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let total = self.total().max(1) as f64;
+        writeln!(
+            formatter,
+            "{:>6} {:>6} {:>7} {:>6}  path",
+            "total", "prefix", "payload", "share"
+        )?;
+        for (path, report) in self.paths() {
+            write!(
+                formatter,
+                "{:>6} {:>6} {:>7} {:>5.1}%  {path}",
+                report.total(),
+                report.prefix,
+                report.payload,
+                report.total() as f64 * 100.0 / total,
+            )?;
+            match report.type_name {
+                Some(type_name) if path.is_empty() => write!(formatter, "({type_name})")?,
+                Some(type_name) => write!(formatter, " ({type_name})")?,
+                None => {}
+            }
+            writeln!(formatter)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::size_report;
+    use crate::to_bytes;
+    use serde::Serialize;
+    use serde_bytes::ByteBuf;
+
+    #[derive(Serialize)]
+    struct Header {
+        number: u64,
+        #[serde(with = "serde_bytes")]
+        bloom: [u8; 256],
+    }
+
+    #[derive(Serialize)]
+    struct Transaction {
+        nonce: u64,
+        data: ByteBuf,
+        storage_keys: Vec<ByteBuf>,
+    }
+
+    #[derive(Serialize)]
+    struct Block {
+        header: Header,
+        transactions: Vec<Transaction>,
+    }
+
+    fn block() -> Block {
+        Block {
+            header: Header {
+                number: 1024,
+                bloom: [0xff; 256],
+            },
+            transactions: (0..4)
+                .map(|nonce| Transaction {
+                    nonce,
+                    data: ByteBuf::from(vec![0xaa; nonce as usize * 20]),
+                    storage_keys: vec![ByteBuf::from(vec![0x01; 32]); 2],
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn report_paths() {
+        let block = block();
+        let report = size_report(&block).unwrap();
+
+        assert_eq!(report.type_name, Some("Block"));
+        assert_eq!(report.total(), to_bytes(&block).unwrap().len());
+
+        let bloom = report.get("header.bloom").unwrap();
+        assert_eq!((bloom.prefix, bloom.payload), (3, 256));
+
+        let number = report.get("header.number").unwrap();
+        assert_eq!((number.prefix, number.payload), (1, 2));
+
+        let header = report.get("header").unwrap();
+        assert_eq!(header.type_name, Some("Header"));
+        assert_eq!((header.prefix, header.payload), (3, 259 + 3));
+
+        let data = report.get("transactions[3].data").unwrap();
+        assert_eq!((data.prefix, data.payload), (2, 60));
+        let data = report.get("transactions[0].data").unwrap();
+        assert_eq!((data.prefix, data.payload), (1, 0));
+
+        let key = report.get("transactions[1].storage_keys[1]").unwrap();
+        assert_eq!((key.prefix, key.payload), (1, 32));
+
+        assert!(report.get("header.missing").is_none());
+
+        let paths: Vec<_> = report.paths().into_iter().map(|(path, _)| path).collect();
+        assert_eq!(paths[..4], ["", "header", "header.number", "header.bloom"]);
+        assert_eq!(
+            paths[4..7],
+            ["transactions", "transactions[0]", "transactions[0].nonce"]
+        );
+    }
+
+    #[test]
+    fn report_children_sum() {
+        let report = size_report(&block()).unwrap();
+
+        for (path, field) in report.paths() {
+            println!("{path}...");
+
+            if !field.children.is_empty() {
+                let children: usize = field.children.iter().map(|child| child.total()).sum();
+                assert_eq!(field.payload, children);
+            }
+
+            println!("ok");
+        }
+    }
+
+    #[test]
+    fn report_inlined_tuple() {
+        #[derive(Serialize)]
+        struct Pairs {
+            pair: (u8, u8),
+            last: u8,
+        }
+
+        let report = size_report(&Pairs {
+            pair: (1, 2),
+            last: 3,
+        })
+        .unwrap();
+
+        // tuples are inlined in the enclosing list
+        let pair = report.get("pair").unwrap();
+        assert_eq!((pair.prefix, pair.payload), (0, 2));
+        assert_eq!(report.get("pair[1]").unwrap().payload, 1);
+        assert_eq!(report.total(), 4);
+    }
+
+    #[test]
+    fn report_summary() {
+        #[derive(Serialize)]
+        struct Dog {
+            name: String,
+            tricks: Vec<String>,
+        }
+
+        let dog = Dog {
+            name: String::from("doggo"),
+            tricks: vec![String::from("sit"), String::from("roll")],
+        };
+        let summary = size_report(&dog).unwrap().to_string();
+        let lines: Vec<_> = summary.lines().collect();
+
+        assert_eq!(
+            lines,
+            [
+                " total prefix payload  share  path",
+                "    17      1      16 100.0%  (Dog)",
+                "     6      1       5  35.3%  name",
+                "    10      1       9  58.8%  tricks",
+                "     4      1       3  23.5%  tricks[0]",
+                "     5      1       4  29.4%  tricks[1]",
+            ]
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+
+/// Payloads up to this length are stored inline, which covers the integers up to 128 bits
+/// and the 20 bytes addresses, while keeping `SmallBytes` as large as a `Vec`.
+/// Longer payloads such as hashes are usually turned into a `Vec` when deserialized,
+/// storing them inline would only add a copy.
+pub const INLINE_LEN: usize = 22;
+
+/// The payload of a string item.
+/// Short payloads are stored inline instead of in their own heap allocation.
+#[derive(Clone)]
+pub struct SmallBytes(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Inline {
+        len: u8,
+        data: [u8; INLINE_LEN],
+    },
+    /// A boxed slice rather than a `Vec`, which leaves room for a longer inline payload
+    Heap(Box<[u8]>),
+    /// A slice of the buffer the payload was decoded from, see `unpack_rlp_shared`
+    #[cfg(feature = "bytes")]
+    Shared(bytes::Bytes),
+}
+
+impl SmallBytes {
+    pub const fn new() -> Self {
+        SmallBytes(Repr::Inline {
+            len: 0,
+            data: [0; INLINE_LEN],
+        })
+    }
+
+    pub fn from_slice(bytes: &[u8]) -> Self {
+        if bytes.len() <= INLINE_LEN {
+            let mut data = [0; INLINE_LEN];
+            data[..bytes.len()].copy_from_slice(bytes);
+            SmallBytes(Repr::Inline {
+                len: bytes.len() as u8,
+                data,
+            })
+        } else {
+            SmallBytes(Repr::Heap(bytes.into()))
+        }
+    }
+
+    /// Keep long payloads as a slice of `bytes` instead of copying them.
+    #[cfg(feature = "bytes")]
+    pub fn from_shared(bytes: bytes::Bytes) -> Self {
+        if bytes.len() <= INLINE_LEN {
+            SmallBytes::from_slice(&bytes)
+        } else {
+            SmallBytes(Repr::Shared(bytes))
+        }
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        match &self.0 {
+            Repr::Inline { len, data } => &data[..*len as usize],
+            Repr::Heap(bytes) => bytes,
+            #[cfg(feature = "bytes")]
+            Repr::Shared(bytes) => bytes,
+        }
+    }
+
+    /// Shared payloads are copied on the first mutable access.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        #[cfg(feature = "bytes")]
+        if let Repr::Shared(bytes) = &self.0 {
+            self.0 = Repr::Heap(bytes.as_ref().into());
+        }
+        match &mut self.0 {
+            Repr::Inline { len, data } => &mut data[..*len as usize],
+            Repr::Heap(bytes) => bytes,
+            #[cfg(feature = "bytes")]
+            Repr::Shared(_) => unreachable!("copied above"),
+        }
+    }
+
+    /// Whether the payload is stored inline, without a heap allocation.
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        match self.0 {
+            Repr::Inline { len, data } => data[..len as usize].to_vec(),
+            Repr::Heap(bytes) => bytes.into_vec(),
+            #[cfg(feature = "bytes")]
+            Repr::Shared(bytes) => bytes.into(),
+        }
+    }
+}
+
+impl Default for SmallBytes {
+    fn default() -> Self {
+        SmallBytes::new()
+    }
+}
+
+impl Deref for SmallBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for SmallBytes {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl AsRef<[u8]> for SmallBytes {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Borrow<[u8]> for SmallBytes {
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl fmt::Debug for SmallBytes {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), formatter)
+    }
+}
+
+impl From<&[u8]> for SmallBytes {
+    fn from(bytes: &[u8]) -> Self {
+        SmallBytes::from_slice(bytes)
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for SmallBytes {
+    fn from(bytes: [u8; N]) -> Self {
+        SmallBytes::from_slice(&bytes)
+    }
+}
+
+/// Keeps the allocation of long payloads without spare capacity, short ones are moved inline.
+impl From<Vec<u8>> for SmallBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        if bytes.len() <= INLINE_LEN {
+            SmallBytes::from_slice(&bytes)
+        } else {
+            SmallBytes(Repr::Heap(bytes.into_boxed_slice()))
+        }
+    }
+}
+
+impl From<SmallBytes> for Vec<u8> {
+    fn from(bytes: SmallBytes) -> Self {
+        bytes.into_vec()
+    }
+}
+
+impl PartialEq for SmallBytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for SmallBytes {}
+
+impl PartialEq<[u8]> for SmallBytes {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl PartialEq<&[u8]> for SmallBytes {
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl PartialEq<Vec<u8>> for SmallBytes {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for SmallBytes {
+    fn eq(&self, other: &[u8; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl PartialEq<SmallBytes> for [u8] {
+    fn eq(&self, other: &SmallBytes) -> bool {
+        self == other.as_slice()
+    }
+}
+
+impl PartialEq<SmallBytes> for &[u8] {
+    fn eq(&self, other: &SmallBytes) -> bool {
+        *self == other.as_slice()
+    }
+}
+
+impl PartialEq<SmallBytes> for Vec<u8> {
+    fn eq(&self, other: &SmallBytes) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl PartialOrd for SmallBytes {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SmallBytes {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl Hash for SmallBytes {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SmallBytes, INLINE_LEN};
+    use crate::RecursiveBytes;
+
+    #[test]
+    fn inline_or_heap() {
+        let tests = [0, 1, 20, INLINE_LEN, INLINE_LEN + 1, 256];
+
+        for (i, len) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            let bytes: Vec<u8> = (0..len).map(|b| b as u8).collect();
+            let small = SmallBytes::from_slice(&bytes);
+            assert_eq!(small.is_inline(), len <= INLINE_LEN);
+            assert_eq!(small, bytes);
+            assert_eq!(SmallBytes::from(bytes.clone()), small);
+            assert_eq!(small.into_vec(), bytes);
+
+            println!("ok");
+        }
+    }
+
+    #[test]
+    fn vec_compatibility() {
+        // what used to be written with a `Vec` payload
+        let rec = RecursiveBytes::Bytes(vec![0x01; 40].into());
+        let RecursiveBytes::Bytes(bytes) = rec else {
+            panic!("expected a string");
+        };
+        assert_eq!(bytes.len(), 40);
+        assert_eq!(&bytes[..2], [0x01, 0x01]);
+        assert_eq!(vec![0x01; 40], bytes);
+        assert_eq!(bytes.to_vec(), Vec::from(bytes));
+    }
+
+    #[test]
+    #[cfg(not(feature = "bytes"))]
+    fn same_size_as_vec() {
+        assert_eq!(
+            core::mem::size_of::<SmallBytes>(),
+            core::mem::size_of::<Vec<u8>>()
+        );
+    }
+
+    #[test]
+    fn mutate_in_place() {
+        let mut small = SmallBytes::from([1, 2, 3]);
+        small[0] = 0xff;
+        assert_eq!(small, [0xff, 2, 3]);
+        assert_eq!(small.len(), 3);
+        assert!(SmallBytes::new().is_empty());
+    }
+}
@@ -0,0 +1,256 @@
+//! `proptest` strategies to property test RLP types: arbitrary `RecursiveBytes` trees,
+//! their canonical encodings, and non-canonical encodings labeled with the error they trigger.
+
+use crate::{append_rlp_bytes, pack_rlp, serialize_list_len, RecursiveBytes, RlpError};
+use alloc::vec::Vec;
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::select;
+
+/// Shape of the generated trees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeParams {
+    /// Maximum nesting of lists
+    pub depth: u32,
+    /// Maximum number of items in a list
+    pub width: usize,
+    /// Maximum length of a string, above 55 to get long strings
+    pub max_len: usize,
+}
+
+impl Default for TreeParams {
+    fn default() -> Self {
+        TreeParams {
+            depth: 4,
+            width: 8,
+            max_len: 64,
+        }
+    }
+}
+
+/// A string payload, single bytes being frequent as they are encoded without a prefix.
+fn payload(max_len: usize) -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        any::<u8>().prop_map(|byte| alloc::vec![byte]),
+        vec(any::<u8>(), 0..=max_len),
+    ]
+}
+
+fn encode_string(bytes: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    append_rlp_bytes(&mut encoded, bytes).expect("a string shorter than 2^64 bytes");
+    encoded
+}
+
+/// Trees of at most `params.depth` nested lists of at most `params.width` items.
+pub fn recursive_bytes(params: TreeParams) -> impl Strategy<Value = RecursiveBytes> {
+    let leaf = payload(params.max_len).prop_map(|bytes| RecursiveBytes::Bytes(bytes.into()));
+    leaf.prop_recursive(params.depth, 256, params.width as u32, move |inner| {
+        vec(inner, 0..=params.width).prop_map(RecursiveBytes::Nested)
+    })
+}
+
+impl Arbitrary for RecursiveBytes {
+    type Parameters = TreeParams;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(params: Self::Parameters) -> Self::Strategy {
+        recursive_bytes(params).boxed()
+    }
+}
+
+/// Canonical encodings of a single item, accepted by `unpack_rlp` and packed back as is.
+pub fn canonical(params: TreeParams) -> impl Strategy<Value = Vec<u8>> {
+    recursive_bytes(params).prop_map(|rec| pack_rlp(rec.into_rlp()).expect("a valid tree"))
+}
+
+/// A deliberate violation of the canonical encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+    /// A single byte below 0x80 encoded as a string of one byte, e.g. `0x81 0x00`
+    PrefixedByte,
+    /// A length of 0 to 55 bytes in the long form, e.g. `0xb8 0x02 0x80 0x80`
+    LongFormShortLength,
+    /// A long length starting with a zero byte, e.g. `0xb9 0x00 0x38 ...`
+    LeadingZeroLength,
+    /// An item missing its last byte
+    Truncated,
+}
+
+impl Mutation {
+    pub const ALL: [Mutation; 4] = [
+        Mutation::PrefixedByte,
+        Mutation::LongFormShortLength,
+        Mutation::LeadingZeroLength,
+        Mutation::Truncated,
+    ];
+
+    /// The error returned by `unpack_rlp` on an encoding with this mutation.
+    pub fn error(&self) -> RlpError {
+        match self {
+            Mutation::PrefixedByte => RlpError::InvalidBytes,
+            Mutation::LongFormShortLength => RlpError::InvalidLength,
+            Mutation::LeadingZeroLength => RlpError::InvalidLength,
+            Mutation::Truncated => RlpError::MissingBytes,
+        }
+    }
+
+    /// An item with the mutation, a string or a list.
+    fn item(self, params: TreeParams) -> BoxedStrategy<Vec<u8>> {
+        // strings of 8 bytes, so that 7 of them make a long list
+        let items = |len| {
+            vec(vec(any::<u8>(), 8), len)
+                .prop_map(|items| items.iter().flat_map(|item| encode_string(item)).collect())
+        };
+        match self {
+            Mutation::PrefixedByte => (0..0x80u8).prop_map(|byte| alloc::vec![0x81, byte]).boxed(),
+            Mutation::LongFormShortLength => (
+                select(alloc::vec![0xb8, 0xf8]),
+                prop_oneof![vec(any::<u8>(), 0..=55), items(0..=6)],
+            )
+                .prop_map(|(disc, payload)| {
+                    let mut item = alloc::vec![disc, payload.len() as u8];
+                    item.extend(payload);
+                    item
+                })
+                .boxed(),
+            Mutation::LeadingZeroLength => (
+                select(alloc::vec![0xb7, 0xf7]),
+                prop_oneof![vec(any::<u8>(), 56..=params.max_len.max(56)), items(7..=10)],
+            )
+                .prop_map(|(disc, payload)| {
+                    let len = payload.len().to_be_bytes();
+                    let len = &len[len.iter().position(|byte| *byte > 0).unwrap_or(len.len())..];
+                    let mut item = alloc::vec![disc + 1 + len.len() as u8, 0];
+                    item.extend_from_slice(len);
+                    item.extend(payload);
+                    item
+                })
+                .boxed(),
+            // an item of a single byte below 0x80 has no prefix, there would be nothing missing
+            Mutation::Truncated => canonical(params)
+                .prop_filter("an item with a prefix", |item| item.len() > 1)
+                .prop_map(|mut item| {
+                    item.pop();
+                    item
+                })
+                .boxed(),
+        }
+    }
+}
+
+/// An encoding that `unpack_rlp` must reject with `error`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonCanonical {
+    pub bytes: Vec<u8>,
+    pub mutation: Mutation,
+    pub error: RlpError,
+}
+
+/// Non-canonical encodings of a single item, the mutated item being nested in up to
+/// `params.depth` canonical lists, between canonical siblings.
+pub fn non_canonical(params: TreeParams) -> impl Strategy<Value = NonCanonical> {
+    let siblings = TreeParams {
+        depth: params.depth.min(1),
+        ..params
+    };
+    select(Mutation::ALL.to_vec()).prop_flat_map(move |mutation| {
+        // a truncated item would take its bytes from the next sibling
+        let after = match mutation {
+            Mutation::Truncated => 0,
+            _ => params.width / 2,
+        };
+        let level = (
+            vec(canonical(siblings), 0..=params.width / 2),
+            vec(canonical(siblings), 0..=after),
+        );
+        let levels = vec(level, 0..=params.depth as usize);
+
+        (mutation.item(params), levels).prop_map(move |(item, levels)| {
+            let bytes = levels.into_iter().fold(item, |item, (before, after)| {
+                let mut payload = before.concat();
+                payload.extend(item);
+                payload.extend(after.concat());
+                let mut list = Vec::new();
+                serialize_list_len(&mut list, payload.len());
+                list.extend(payload);
+                list
+            });
+            NonCanonical {
+                bytes,
+                mutation,
+                error: mutation.error(),
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{canonical, non_canonical, recursive_bytes, Mutation, TreeParams};
+    use crate::{pack_rlp, unpack_rlp, RecursiveBytes};
+    use proptest::prelude::*;
+
+    /// depth and width of a tree.
+    fn shape(rec: &RecursiveBytes) -> (u32, usize) {
+        match rec {
+            RecursiveBytes::Nested(recs) => recs.iter().map(shape).fold(
+                (1, recs.len()),
+                |(depth, width), (rec_depth, rec_width)| {
+                    (depth.max(rec_depth + 1), width.max(rec_width))
+                },
+            ),
+            _ => (0, 0),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn tree_shape(rec in recursive_bytes(TreeParams { depth: 3, width: 4, max_len: 8 })) {
+            let (depth, width) = shape(&rec);
+            prop_assert!(depth <= 3);
+            prop_assert!(width <= 4);
+        }
+
+        #[test]
+        fn canonical_round_trip(bytes in canonical(TreeParams::default())) {
+            let rlp = unpack_rlp(&bytes).unwrap();
+            prop_assert_eq!(rlp.len(), 1);
+            prop_assert_eq!(pack_rlp(rlp).unwrap(), bytes);
+        }
+
+        #[test]
+        fn non_canonical_rejected(non_canonical in non_canonical(TreeParams::default())) {
+            prop_assert_eq!(unpack_rlp(&non_canonical.bytes).unwrap_err(), non_canonical.error);
+        }
+
+        #[test]
+        fn arbitrary_tree(rec in any::<RecursiveBytes>()) {
+            let bytes = pack_rlp(rec.into_rlp()).unwrap();
+            prop_assert_eq!(pack_rlp(unpack_rlp(&bytes).unwrap()).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn mutation_errors() {
+        let tests = [
+            ("8100", Mutation::PrefixedByte),
+            ("c3c2817f", Mutation::PrefixedByte),
+            ("b8028080", Mutation::LongFormShortLength),
+            ("f80180", Mutation::LongFormShortLength),
+            ("f800", Mutation::LongFormShortLength),
+            ("c4b900383f", Mutation::LeadingZeroLength),
+            ("c28200", Mutation::Truncated),
+        ];
+
+        for (i, (bytes, mutation)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            let bytes = hex::decode(bytes).unwrap();
+            assert_eq!(unpack_rlp(&bytes).unwrap_err(), mutation.error());
+
+            println!("ok");
+        }
+    }
+}
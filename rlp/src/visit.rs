@@ -0,0 +1,155 @@
+use crate::{read_item_header, RlpError};
+
+/// Callbacks fired while walking over RLP-encoded bytes with [`visit_rlp`].
+/// Offsets are the position of the first byte of the item (its prefix) in the input.
+/// All the callbacks do nothing by default, so only the relevant ones need to be implemented.
+pub trait RlpVisitor {
+    /// A list starts, `len` is the length of its payload in bytes.
+    fn enter_list(&mut self, _len: usize, _offset: usize) {}
+
+    /// The last list that was entered ends.
+    fn exit_list(&mut self) {}
+
+    /// A data item (string), without its prefix.
+    fn data(&mut self, _bytes: &[u8], _offset: usize) {}
+}
+
+/// Walk over RLP-encoded bytes without building any `Rlp` representation.
+/// The input is validated with the same rules as `unpack_rlp`.
+/// Because items are reported as they are read, the visitor may have been
+/// called for the beginning of the input when an error is returned.
+pub fn visit_rlp<V>(bytes: &[u8], visitor: &mut V) -> Result<(), RlpError>
+where
+    V: RlpVisitor + ?Sized,
+{
+    visit_rlp_element(bytes, 0, visitor)
+}
+
+fn visit_rlp_element<V>(bytes: &[u8], mut cursor: usize, visitor: &mut V) -> Result<(), RlpError>
+where
+    V: RlpVisitor + ?Sized,
+{
+    while cursor < bytes.len() {
+        let header = read_item_header(bytes, cursor)?;
+
+        if header.kind.is_list() {
+            visitor.enter_list(header.payload.len(), cursor);
+            visit_rlp_element(&bytes[..header.payload.end], header.payload.start, visitor)?;
+            visitor.exit_list();
+        } else {
+            visitor.data(&bytes[header.payload.clone()], cursor);
+        }
+
+        cursor = header.payload.end;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{visit_rlp, RlpVisitor};
+    use crate::{to_bytes, unpack_rlp, RlpError};
+    use serde_bytes::ByteBuf;
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Enter(usize, usize),
+        Exit,
+        Data(Vec<u8>, usize),
+    }
+
+    #[derive(Default)]
+    struct Recorder(Vec<Event>);
+
+    impl RlpVisitor for Recorder {
+        fn enter_list(&mut self, len: usize, offset: usize) {
+            self.0.push(Event::Enter(len, offset));
+        }
+
+        fn exit_list(&mut self) {
+            self.0.push(Event::Exit);
+        }
+
+        fn data(&mut self, bytes: &[u8], offset: usize) {
+            self.0.push(Event::Data(bytes.to_vec(), offset));
+        }
+    }
+
+    #[test]
+    fn visit_nested() {
+        // [[], [[]], "dog"], 0x0f
+        let bytes = [0xc7, 0xc0, 0xc1, 0xc0, 0x83, b'd', b'o', b'g', 0x0f];
+        let recorder = &mut Recorder::default();
+        visit_rlp(&bytes, recorder).unwrap();
+
+        assert_eq!(
+            recorder.0,
+            vec![
+                Event::Enter(7, 0),
+                Event::Enter(0, 1),
+                Event::Exit,
+                Event::Enter(1, 2),
+                Event::Enter(0, 3),
+                Event::Exit,
+                Event::Exit,
+                Event::Data(b"dog".to_vec(), 4),
+                Event::Exit,
+                Event::Data(vec![0x0f], 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn visit_byte_histogram() {
+        struct Histogram([usize; 256]);
+
+        impl RlpVisitor for Histogram {
+            fn data(&mut self, bytes: &[u8], _offset: usize) {
+                for byte in bytes {
+                    self.0[*byte as usize] += 1;
+                }
+            }
+        }
+
+        let data = vec![
+            ByteBuf::from(vec![0; 100]),
+            ByteBuf::from(vec![1; 60]),
+            ByteBuf::from(vec![0; 3]),
+        ];
+        let bytes = to_bytes(&data).unwrap();
+
+        let histogram = &mut Histogram([0; 256]);
+        visit_rlp(&bytes, histogram).unwrap();
+
+        assert_eq!(histogram.0[0], 100 + 3);
+        assert_eq!(histogram.0[1], 60);
+        assert_eq!(histogram.0.iter().sum::<usize>(), 163);
+    }
+
+    #[test]
+    fn visit_same_errors_as_unpack() {
+        #[rustfmt::skip]
+        let tests = [
+            &[93, 61, 73, 95, 61, 61, 248, 0][..],
+            &[5, 248, 5, 5, 29, 38, 5, 5, 128, 128, 5, 73, 128, 128, 5, 44, 73][..],
+            &[0x81, 0x01][..],
+            &[0xb8, 0x05, 0, 0, 0, 0, 0][..],
+            &[0xc2, 0x83, b'd'][..],
+        ];
+
+        for (i, bytes) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            let visited = visit_rlp(bytes, &mut Recorder::default()).unwrap_err();
+            let unpacked = unpack_rlp(bytes).unwrap_err();
+            assert_eq!(
+                std::mem::discriminant(&visited),
+                std::mem::discriminant(&unpacked)
+            );
+            assert!(!matches!(visited, RlpError::Message(_)));
+
+            println!("ok");
+        }
+    }
+}
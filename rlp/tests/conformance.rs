@@ -0,0 +1,146 @@
+//! Conformance against the RLP test vectors of the Ethereum tests, vendored in `tests/fixtures`.
+//! Set `RLP_TESTS` to the `RLPTests` directory of an ethereum/tests checkout to run those instead.
+
+use rlp_rs::{pack_rlp, unpack_rlp, Rlp, RlpError};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+fn fixture_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files.append(&mut fixture_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
+
+fn fixtures_dir() -> PathBuf {
+    match std::env::var_os("RLP_TESTS") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/RLPTests"),
+    }
+}
+
+fn read_vectors(dir: &Path) -> Vec<(String, Value, Vec<u8>)> {
+    let mut vectors = Vec::new();
+    for file in fixture_files(dir) {
+        let content = std::fs::read_to_string(&file).unwrap();
+        let tests: serde_json::Map<String, Value> = serde_json::from_str(&content).unwrap();
+        for (name, test) in tests {
+            let out = test["out"].as_str().unwrap();
+            let bytes = hex::decode(out.strip_prefix("0x").unwrap()).unwrap();
+            vectors.push((name, test["in"].clone(), bytes));
+        }
+    }
+    vectors
+}
+
+fn vectors() -> Vec<(String, Value, Vec<u8>)> {
+    let vectors = read_vectors(&fixtures_dir());
+    assert!(!vectors.is_empty());
+    vectors
+}
+
+fn expected_error(name: &str) -> RlpError {
+    match name {
+        "bytesShouldBeSingleByte00" | "bytesShouldBeSingleByte01" | "bytesShouldBeSingleByte7F" => {
+            RlpError::InvalidBytes
+        }
+        // lengths in the long form that are not the shortest encoding of the length
+        "wrongSizeList"
+        | "wrongSizeList2"
+        | "incorrectLengthInArray"
+        | "randomRLP"
+        | "leadingZerosInLongLengthArray1"
+        | "leadingZerosInLongLengthArray2"
+        | "leadingZerosInLongLengthList1"
+        | "leadingZerosInLongLengthList2"
+        | "nonOptimalLongLengthArray1"
+        | "nonOptimalLongLengthArray2"
+        | "nonOptimalLongLengthList1"
+        | "nonOptimalLongLengthList2" => RlpError::InvalidLength,
+        // the declared end of the payload overflows
+        "lessThanLongLengthList2" => RlpError::InvalidLength,
+        // the payload is shorter than its declared length
+        "int32Overflow"
+        | "int32Overflow2"
+        | "emptyEncoding"
+        | "lessThanShortLengthArray1"
+        | "lessThanShortLengthArray2"
+        | "lessThanShortLengthList1"
+        | "lessThanShortLengthList2"
+        | "lessThanLongLengthArray1"
+        | "lessThanLongLengthArray2"
+        | "lessThanLongLengthList1" => RlpError::MissingBytes,
+        _ => panic!("{name}: no expected error for this vector"),
+    }
+}
+
+#[test]
+fn valid_vectors() {
+    for (name, input, bytes) in vectors() {
+        if input == "INVALID" {
+            continue;
+        }
+        println!("{name}...");
+
+        let rlp = unpack_rlp(&bytes).unwrap();
+        assert_eq!(pack_rlp(rlp.clone()).unwrap(), bytes);
+
+        if input != "VALID" {
+            let expected = Rlp::from_json(&input).unwrap();
+            assert_eq!(rlp.to_json().unwrap(), expected.to_json().unwrap());
+            assert_eq!(pack_rlp(expected).unwrap(), bytes);
+        }
+
+        println!("ok");
+    }
+}
+
+#[test]
+fn invalid_vectors() {
+    for (name, input, bytes) in vectors() {
+        if input != "INVALID" {
+            continue;
+        }
+        println!("{name}...");
+
+        let err = unpack_rlp(&bytes).unwrap_err();
+        assert_eq!(
+            std::mem::discriminant(&err),
+            std::mem::discriminant(&expected_error(&name)),
+            "{name}: {err}"
+        );
+
+        println!("ok");
+    }
+}
+
+/// The random vectors are valid items, checked to survive an unpack and pack round trip.
+#[test]
+fn random_vectors() {
+    let dir = fixtures_dir().join("RandomRLPTests");
+    if !dir.is_dir() {
+        println!("no RandomRLPTests in {}", dir.display());
+        return;
+    }
+
+    let vectors = read_vectors(&dir);
+    assert!(!vectors.is_empty());
+    for (name, input, bytes) in vectors {
+        println!("{name}...");
+
+        assert_eq!(input, "VALID");
+        let rlp = unpack_rlp(&bytes).unwrap();
+        let packed = pack_rlp(rlp.clone()).unwrap();
+        assert_eq!(packed, bytes);
+        assert_eq!(unpack_rlp(&packed).unwrap().to_json(), rlp.to_json());
+
+        println!("ok");
+    }
+}
@@ -1,10 +1,14 @@
 use crate::primitives::{Address, Bloom, Nonce, U256};
 use crate::{TransactionEnvelope, B32};
+use alloc::vec::Vec;
+#[cfg(feature = "fuzzing")]
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
 use rlp_rs::{unpack_rlp, RecursiveBytes, Rlp, RlpError};
 use serde::ser::SerializeStruct;
 use serde::{de, Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 
+#[cfg_attr(feature = "fuzzing", derive(Arbitrary))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize)]
 pub struct Block {
     pub header: Header,
@@ -22,6 +26,9 @@ impl Block {
 
     fn _from_bytes(bytes: &[u8], unknown: bool) -> Result<Self, RlpError> {
         let raw_rlp = unpack_rlp(bytes)?;
+        if raw_rlp.len() > 1 {
+            return Err(RlpError::TrailingBytes);
+        }
 
         let rlp_iter = &mut raw_rlp.into_iter();
         let rlp_inner = &mut rlp_iter.next().ok_or(RlpError::MissingBytes)?;
@@ -76,6 +83,7 @@ impl Block {
     }
 }
 
+#[cfg_attr(feature = "fuzzing", derive(Arbitrary))]
 #[derive(Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Clone, Default)]
 pub struct CommonHeader {
     pub parent_hash: B32,
@@ -113,6 +121,7 @@ macro_rules! define_header {
             }
         ),* $(,)?
     ) => {
+        #[cfg_attr(feature = "fuzzing", derive(Arbitrary))]
         #[derive(Debug, PartialEq, Eq, Hash, Clone)]
         pub enum Header {
             $(
@@ -142,6 +151,7 @@ macro_rules! define_header {
     };
 }
 
+#[cfg_attr(feature = "fuzzing", derive(Arbitrary))]
 #[derive(Debug, Serialize, PartialEq, Eq, Hash, Clone)]
 pub struct Bytes(#[serde(with = "serde_bytes")] Vec<u8>);
 
@@ -509,7 +519,7 @@ impl Header {
         let mut rest = Vec::new();
         while let Some(rec) = rlp.pop_front() {
             let bytes = match rec {
-                RecursiveBytes::Bytes(bytes) => bytes.into(),
+                RecursiveBytes::Bytes(bytes) => bytes.into_vec().into(),
                 _ => return Err(RlpError::ExpectedBytes),
             };
             rest.push(bytes);
@@ -533,7 +543,7 @@ struct HeaderVisitor;
 impl<'de> de::Visitor<'de> for HeaderVisitor {
     type Value = Header;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
         formatter.write_str("a well formed RLP-encoded Header")
     }
 
@@ -1219,6 +1229,20 @@ mod tests {
         assert_eq!(block, block2);
     }
 
+    #[test]
+    fn block_trailing_items() {
+        let mut bytes = rlp_rs::to_bytes(&Block::default()).unwrap();
+        bytes.push(0x80);
+        assert_eq!(
+            Block::from_bytes(&bytes).unwrap_err(),
+            RlpError::TrailingBytes
+        );
+        assert_eq!(
+            Block::unknown_from_bytes(&bytes).unwrap_err(),
+            RlpError::TrailingBytes
+        );
+    }
+
     #[test]
     fn block_unknown() {
         let block = Block {
@@ -0,0 +1,267 @@
+//! JSON views of decoded transactions and blocks, in the shape printed by `rlp-tx --json`:
+//! quantities are hex numbers without leading zeros, data is `0x` hex.
+
+use crate::{Block, Field, Header, TransactionEnvelope};
+use alloc::{format, string::ToString, vec::Vec};
+use rlp_rs::RlpError;
+use serde_json::{json, Map, Value};
+
+fn data(bytes: impl AsRef<[u8]>) -> Value {
+    Value::String(format!("0x{}", hex::encode(bytes)))
+}
+
+/// a big-endian integer, `0x0` for zero.
+fn quantity(bytes: impl AsRef<[u8]>) -> Value {
+    let digits = hex::encode(bytes);
+    let digits = digits.trim_start_matches('0');
+    Value::String(format!(
+        "0x{}",
+        if digits.is_empty() { "0" } else { digits }
+    ))
+}
+
+fn uint(value: u64) -> Value {
+    Value::String(format!("{value:#x}"))
+}
+
+/// The JSON value of a transaction field.
+pub fn field(field: &Field) -> Value {
+    match field {
+        Field::Quantity(bytes) => quantity(bytes),
+        Field::Uint(value) => uint(*value),
+        Field::Data(bytes) => data(bytes),
+        Field::Words(words) => words.iter().map(data).collect(),
+        Field::AccessList(access_list) => access_list
+            .iter()
+            .map(|(address, keys)| {
+                json!({
+                    "address": data(address),
+                    "storage_keys": keys.iter().map(data).collect::<Value>(),
+                })
+            })
+            .collect(),
+    }
+}
+
+/// The type, name, hash and fields of a transaction.
+pub fn transaction(tx: &TransactionEnvelope) -> Result<Value, RlpError> {
+    let fields: Map<_, _> = tx
+        .fields()?
+        .iter()
+        .map(|(name, value)| (name.to_string(), field(value)))
+        .collect();
+
+    Ok(json!({
+        "type": tx.tx_type(),
+        "name": tx.name(),
+        "hash": data(tx.hash()?),
+        "fields": fields,
+    }))
+}
+
+/// The fork and fields of a header.
+pub fn header(header: &Header) -> Value {
+    let mut fields = json!({
+        "parent_hash": data(header.parent_hash()),
+        "uncle_hash": data(header.uncle_hash()),
+        "coinbase": data(header.coinbase()),
+        "state_root": data(header.state_root()),
+        "tx_root": data(header.tx_root()),
+        "receipt_hash": data(header.receipt_hash()),
+        "bloom": data(header.bloom()),
+        "difficulty": quantity(header.difficulty()),
+        "number": quantity(header.number()),
+        "gas_limit": uint(*header.gas_limit()),
+        "gas_used": uint(*header.gas_used()),
+        "time": uint(*header.time()),
+        "extra": data(header.extra()),
+        "mix_digest": data(header.mix_digest()),
+        "nonce": data(header.nonce()),
+    });
+
+    let fork = match header {
+        Header::Legacy { .. } => "legacy",
+        Header::London { base_fee, .. } => {
+            fields["base_fee"] = quantity(base_fee);
+            "london"
+        }
+        Header::Shanghai {
+            base_fee,
+            withdrawal_root,
+            ..
+        } => {
+            fields["base_fee"] = quantity(base_fee);
+            fields["withdrawal_root"] = data(withdrawal_root);
+            "shanghai"
+        }
+        Header::Cancun {
+            base_fee,
+            withdrawal_root,
+            blob_gas_used,
+            excess_blob_gas,
+            parent_beacon_block_root,
+            ..
+        } => {
+            fields["base_fee"] = quantity(base_fee);
+            fields["withdrawal_root"] = data(withdrawal_root);
+            fields["blob_gas_used"] = uint(*blob_gas_used);
+            fields["excess_blob_gas"] = uint(*excess_blob_gas);
+            fields["parent_beacon_block_root"] = data(parent_beacon_block_root);
+            "cancun"
+        }
+        Header::Unknown { rest, .. } => {
+            fields["rest"] = rest
+                .iter()
+                .map(|bytes| data(Vec::from(bytes.clone())))
+                .collect();
+            "unknown"
+        }
+    };
+
+    json!({
+        "fork": fork,
+        "fields": fields,
+    })
+}
+
+/// The hash, header, transactions and uncles of a block.
+pub fn block(block: &Block) -> Result<Value, RlpError> {
+    Ok(json!({
+        "hash": data(block.hash()?),
+        "header": header(&block.header),
+        "transactions": block
+            .transactions
+            .iter()
+            .map(transaction)
+            .collect::<Result<Vec<_>, _>>()?,
+        "uncles": block.uncles.iter().map(header).collect::<Value>(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{block, header, quantity, transaction, uint};
+    use crate::{Block, Header, TransactionEnvelope, U256};
+    use serde_json::json;
+
+    #[test]
+    fn quantities() {
+        let tests = [
+            (U256::default(), "0x0"),
+            (U256::from([0; 32]), "0x0"),
+            (vec![0x01, 0x00].try_into().unwrap(), "0x100"),
+            (vec![0x0a].try_into().unwrap(), "0xa"),
+        ];
+
+        for (i, (value, expected)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            assert_eq!(quantity(&value), json!(expected));
+
+            println!("ok");
+        }
+
+        assert_eq!(uint(0), json!("0x0"));
+        assert_eq!(uint(0x1e241), json!("0x1e241"));
+    }
+
+    #[test]
+    fn dynamic_fee_transaction() {
+        let bytes = hex::decode("b8a302f8a0018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8").unwrap();
+        let tx = TransactionEnvelope::from_bytes(&bytes).unwrap();
+        let value = transaction(&tx).unwrap();
+
+        assert_eq!(value["type"], 2);
+        assert_eq!(value["name"], "dynamic fee");
+        assert_eq!(
+            value["hash"],
+            format!("0x{}", hex::encode(tx.hash().unwrap()))
+        );
+
+        let fields = &value["fields"];
+        assert_eq!(fields["chain_id"], "0x1");
+        assert_eq!(fields["nonce"], "0x0");
+        assert_eq!(fields["max_fee_per_gas"], "0x3b9aca00");
+        assert_eq!(fields["gas_limit"], "0x1e241");
+        assert_eq!(
+            fields["destination"],
+            "0x095e7baea6a6c7c4c2dfeb977efac326af552d87"
+        );
+        assert_eq!(fields["data"], "0x");
+        assert_eq!(
+            fields["access_list"],
+            json!([{
+                "address": "0x0000000000000000000000000000000000000001",
+                "storage_keys": [format!("0x{}", "0".repeat(64))],
+            }])
+        );
+    }
+
+    #[test]
+    fn london_block() {
+        let bytes = hex::decode("f9030bf901fea083cafc574e1f51ba9dc0568fc617a08ea2429fb384059c972f13b19fa1c8dd55a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347948888f1f195afa192cfee860698584c030f4c9db1a0ef1552a40b7165c3cd773806b9e0c165b75356e0314bf0706f279c729f51e017a05fe50b260da6308036625b850b5d6ced6d0a9f814c0688bc91ffb7b7a3a54b67a0bc37d79753ad738a6dac4921e57392f145d8887476de3f783dfa7edae9283e52b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008302000001832fefd8825208845506eb0780a0bd4472abb6659ebe3ee06ee4d7b72a00a9f4d001caca51342001075469aff49888a13a5a8c8f2bb1c4843b9aca00f90106f85f800a82c35094095e7baea6a6c7c4c2dfeb977efac326af552d870a801ba09bea4c4daac7c7c52e093e6a4c35dbbcf8856f1af7b059ba20253e70848d094fa08a8fae537ce25ed8cb5af9adac3f141af69bd515bd2ba031522df09b97dd72b1b8a302f8a0018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8c0").unwrap();
+        let value = block(&Block::from_bytes(&bytes).unwrap()).unwrap();
+
+        let header = &value["header"];
+        assert_eq!(header["fork"], "london");
+        assert_eq!(
+            header["fields"]["coinbase"],
+            "0x8888f1f195afa192cfee860698584c030f4c9db1"
+        );
+        assert_eq!(header["fields"]["difficulty"], "0x20000");
+        assert_eq!(header["fields"]["number"], "0x1");
+        assert_eq!(header["fields"]["gas_used"], "0x5208");
+        assert_eq!(header["fields"]["extra"], "0x");
+        assert_eq!(header["fields"]["nonce"], "0xa13a5a8c8f2bb1c4");
+        assert_eq!(header["fields"]["base_fee"], "0x3b9aca00");
+
+        let transactions = value["transactions"].as_array().unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0]["name"], "legacy");
+        assert_eq!(transactions[0]["fields"]["v"], "0x1b");
+        assert_eq!(transactions[1]["name"], "dynamic fee");
+        assert_eq!(value["uncles"], json!([]));
+    }
+
+    #[test]
+    fn header_forks() {
+        let tests = [
+            (Header::default(), "legacy", None),
+            (
+                Header::Unknown {
+                    parent_hash: Default::default(),
+                    uncle_hash: Default::default(),
+                    coinbase: Default::default(),
+                    state_root: Default::default(),
+                    tx_root: Default::default(),
+                    receipt_hash: Default::default(),
+                    bloom: Default::default(),
+                    difficulty: Default::default(),
+                    number: Default::default(),
+                    gas_limit: 0,
+                    gas_used: 0,
+                    time: 0,
+                    extra: vec![],
+                    mix_digest: Default::default(),
+                    nonce: Default::default(),
+                    rest: vec![vec![0xff].into()],
+                },
+                "unknown",
+                Some(("rest", json!(["0xff"]))),
+            ),
+        ];
+
+        for (i, (value, fork, extra_field)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            let value = header(&value);
+            assert_eq!(value["fork"], fork);
+            assert_eq!(value["fields"]["gas_limit"], "0x0");
+            if let Some((name, field)) = extra_field {
+                assert_eq!(value["fields"][name], field);
+            }
+
+            println!("ok");
+        }
+    }
+}
@@ -1,8 +1,16 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 mod block;
+#[cfg(feature = "json")]
+pub mod json;
 mod primitives;
 mod transaction;
+mod view;
 
 pub use block::*;
 pub use primitives::*;
 pub use rlp_rs::RlpError;
 pub use transaction::*;
+pub use view::*;
@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 #[cfg(feature = "fuzzing")]
 use libfuzzer_sys::arbitrary::{self, Arbitrary};
 use serde::{Deserialize, Serialize};
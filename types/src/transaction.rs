@@ -1,7 +1,8 @@
 use crate::primitives::{Address, U256};
+use alloc::{vec, vec::Vec};
 #[cfg(feature = "fuzzing")]
 use libfuzzer_sys::arbitrary::{self, Arbitrary};
-use rlp_rs::{unpack_rlp, RecursiveBytes, Rlp, RlpError};
+use rlp_rs::{pack_rlp, unpack_rlp, RecursiveBytes, Rlp, RlpError, SmallBytes};
 use serde::{ser::SerializeTuple, Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 
@@ -153,10 +154,32 @@ impl TransactionEnvelope {
         let res = Self::from_raw_rlp(&mut rlp)?;
         match rlp.is_empty() {
             true => Ok(res),
-            false => Err(RlpError::InvalidLength),
+            false => Err(RlpError::TrailingBytes),
         }
     }
 
+    /// Decode a raw transaction, as sent to `eth_sendRawTransaction`,
+    /// or as found in a block body where typed transactions are wrapped in a string.
+    pub fn from_raw_bytes(bytes: &[u8]) -> Result<Self, RlpError> {
+        match bytes.first() {
+            Some(tx_type) if *tx_type < 0x80 => {
+                let wrapped = Rlp::new_unary(RecursiveBytes::Bytes(SmallBytes::from_slice(bytes)));
+                Self::from_bytes(&pack_rlp(wrapped)?)
+            }
+            _ => Self::from_bytes(bytes),
+        }
+    }
+
+    /// The raw transaction, as sent to `eth_sendRawTransaction`:
+    /// the type of a typed transaction followed by its fields, unwrapped.
+    pub fn to_raw_bytes(&self) -> Result<Vec<u8>, RlpError> {
+        let mut bytes = self.bytes()?;
+        if self.tx_type() > 0 {
+            bytes.insert(0, self.tx_type());
+        }
+        Ok(bytes)
+    }
+
     /// decode an rlp encoded transaction with an expected tx_type
     fn decode_transaction(rlp: &mut Rlp, tx_type: u8) -> Result<Self, RlpError> {
         // TODO could we use tx_type here ? Maybe using an enum instead of a num
@@ -190,7 +213,12 @@ impl TransactionEnvelope {
             Some(nest) => (0, &mut Rlp::new_unary(nest)),
         };
 
-        Self::decode_transaction(tx_rlp, tx_type)
+        let tx = Self::decode_transaction(tx_rlp, tx_type)?;
+        // the payload of a typed transaction is a single list
+        match tx_rlp.is_empty() {
+            true => Ok(tx),
+            false => Err(RlpError::TrailingBytes),
+        }
     }
 }
 
@@ -390,4 +418,66 @@ mod tests {
 
         assert!(TransactionEnvelope::from_bytes(&bytes).is_err());
     }
+
+    #[test]
+    fn trailing_items() {
+        let legacy = "f85f800a82c35094095e7baea6a6c7c4c2dfeb977efac326af552d870a801ba09bea4c4daac7c7c52e093e6a4c35dbbcf8856f1af7b059ba20253e70848d094fa08a8fae537ce25ed8cb5af9adac3f141af69bd515bd2ba031522df09b97dd72b1";
+        let dynamic_fee = "02f8a0018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8";
+
+        let tests = [
+            format!("{legacy}80"),
+            // an item after the payload of a typed transaction
+            format!("b8a4{dynamic_fee}80"),
+            format!("b8a3{dynamic_fee}80"),
+        ];
+
+        assert!(TransactionEnvelope::from_bytes(&hex::decode(legacy).unwrap()).is_ok());
+        assert!(TransactionEnvelope::from_bytes(
+            &hex::decode(format!("b8a3{dynamic_fee}")).unwrap()
+        )
+        .is_ok());
+        for (i, bytes) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            let bytes = hex::decode(bytes).unwrap();
+            assert_eq!(
+                TransactionEnvelope::from_bytes(&bytes).unwrap_err(),
+                RlpError::TrailingBytes
+            );
+
+            println!("ok");
+        }
+    }
+
+    #[test]
+    fn raw_bytes() {
+        let legacy = "f85f800a82c35094095e7baea6a6c7c4c2dfeb977efac326af552d870a801ba09bea4c4daac7c7c52e093e6a4c35dbbcf8856f1af7b059ba20253e70848d094fa08a8fae537ce25ed8cb5af9adac3f141af69bd515bd2ba031522df09b97dd72b1";
+        let dynamic_fee = "02f8a0018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8";
+
+        let tests = [
+            (legacy.to_string(), 0, legacy),
+            (dynamic_fee.to_string(), 2, dynamic_fee),
+            // as found in a block body
+            (format!("b8a3{dynamic_fee}"), 2, dynamic_fee),
+        ];
+
+        for (i, (bytes, tx_type, raw)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            let tx = TransactionEnvelope::from_raw_bytes(&hex::decode(bytes).unwrap()).unwrap();
+            assert_eq!(tx.tx_type(), tx_type);
+            assert_eq!(hex::encode(tx.to_raw_bytes().unwrap()), raw);
+
+            println!("ok");
+        }
+
+        assert_eq!(
+            TransactionEnvelope::from_raw_bytes(&[]).unwrap_err(),
+            RlpError::MissingBytes
+        );
+        assert_eq!(
+            TransactionEnvelope::from_raw_bytes(&[0x05, 0xc0]).unwrap_err(),
+            RlpError::InvalidBytes
+        );
+    }
 }
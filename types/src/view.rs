@@ -0,0 +1,206 @@
+//! The fields of a transaction as shown to users, shared by the command-line tool and the bindings.
+
+use crate::primitives::U256;
+use crate::transaction::{AccessList, TransactionEnvelope};
+use alloc::{vec, vec::Vec};
+use rlp_rs::RlpError;
+
+/// A field of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Field<'a> {
+    /// A big-endian integer.
+    Quantity(&'a [u8]),
+    Uint(u64),
+    Data(&'a [u8]),
+    /// 32 bytes words stored as integers, such as blob hashes.
+    Words(Vec<[u8; 32]>),
+    /// Addresses and their storage keys.
+    AccessList(Vec<(&'a [u8], Vec<[u8; 32]>)>),
+}
+
+/// `value` with its leading zeros, or `InvalidLength` when it doesn't fit in 32 bytes.
+pub fn word(value: &U256) -> Result<[u8; 32], RlpError> {
+    let bytes = value.as_ref();
+    if bytes.len() > 32 {
+        return Err(RlpError::InvalidLength);
+    }
+
+    let mut word = [0; 32];
+    word[32 - bytes.len()..].copy_from_slice(bytes);
+    Ok(word)
+}
+
+fn words(values: &[U256]) -> Result<Field<'_>, RlpError> {
+    Ok(Field::Words(
+        values.iter().map(word).collect::<Result<_, _>>()?,
+    ))
+}
+
+fn access_list(access_list: &[AccessList]) -> Result<Field<'_>, RlpError> {
+    let access_list = access_list
+        .iter()
+        .map(|item| {
+            let keys = item
+                .storage_keys
+                .iter()
+                .map(word)
+                .collect::<Result<_, _>>()?;
+            Ok((item.address.as_ref(), keys))
+        })
+        .collect::<Result<_, RlpError>>()?;
+    Ok(Field::AccessList(access_list))
+}
+
+fn quantity(value: &U256) -> Field<'_> {
+    Field::Quantity(value.as_ref())
+}
+
+impl TransactionEnvelope {
+    /// `legacy`, `access list`, `dynamic fee` or `blob`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransactionEnvelope::Legacy(_) => "legacy",
+            TransactionEnvelope::AccessList(_) => "access list",
+            TransactionEnvelope::DynamicFee(_) => "dynamic fee",
+            TransactionEnvelope::Blob(_) => "blob",
+        }
+    }
+
+    /// The fields of the transaction in encoding order,
+    /// or `InvalidLength` when a storage key or a blob hash is longer than 32 bytes.
+    pub fn fields(&self) -> Result<Vec<(&'static str, Field<'_>)>, RlpError> {
+        let fields = match self {
+            TransactionEnvelope::Legacy(tx) => vec![
+                ("nonce", Field::Uint(tx.nonce)),
+                ("gas_price", quantity(&tx.gas_price)),
+                ("gas_limit", Field::Uint(tx.gas_limit)),
+                ("to", Field::Data(tx.to.as_ref())),
+                ("value", quantity(&tx.value)),
+                ("data", Field::Data(&tx.data)),
+                ("v", quantity(&tx.v)),
+                ("r", quantity(&tx.r)),
+                ("s", quantity(&tx.s)),
+            ],
+            TransactionEnvelope::AccessList(tx) => vec![
+                ("chain_id", quantity(&tx.chain_id)),
+                ("nonce", Field::Uint(tx.nonce)),
+                ("gas_price", quantity(&tx.gas_price)),
+                ("gas_limit", Field::Uint(tx.gas_limit)),
+                ("to", Field::Data(tx.to.as_ref())),
+                ("value", quantity(&tx.value)),
+                ("data", Field::Data(&tx.data)),
+                ("access_list", access_list(&tx.access_list)?),
+                ("y_parity", quantity(&tx.y_parity)),
+                ("r", quantity(&tx.r)),
+                ("s", quantity(&tx.s)),
+            ],
+            TransactionEnvelope::DynamicFee(tx) => vec![
+                ("chain_id", quantity(&tx.chain_id)),
+                ("nonce", Field::Uint(tx.nonce)),
+                (
+                    "max_priority_fee_per_gas",
+                    quantity(&tx.max_priority_fee_per_gas),
+                ),
+                ("max_fee_per_gas", quantity(&tx.max_fee_per_gas)),
+                ("gas_limit", Field::Uint(tx.gas_limit)),
+                ("destination", Field::Data(tx.destination.as_ref())),
+                ("amount", quantity(&tx.amount)),
+                ("data", Field::Data(&tx.data)),
+                ("access_list", access_list(&tx.access_list)?),
+                ("y_parity", quantity(&tx.y_parity)),
+                ("r", quantity(&tx.r)),
+                ("s", quantity(&tx.s)),
+            ],
+            TransactionEnvelope::Blob(tx) => vec![
+                ("chain_id", quantity(&tx.chain_id)),
+                ("nonce", Field::Uint(tx.nonce)),
+                (
+                    "max_priority_fee_per_gas",
+                    quantity(&tx.max_priority_fee_per_gas),
+                ),
+                ("max_fee_per_gas", quantity(&tx.max_fee_per_gas)),
+                ("gas_limit", Field::Uint(tx.gas_limit)),
+                ("to", Field::Data(tx.to.as_ref())),
+                ("value", quantity(&tx.value)),
+                ("data", Field::Data(&tx.data)),
+                ("access_list", access_list(&tx.access_list)?),
+                ("max_fee_per_blob_gas", quantity(&tx.max_fee_per_blob_gas)),
+                ("blob_hashes", words(&tx.blob_hashes)?),
+                ("y_parity", quantity(&tx.y_parity)),
+                ("r", quantity(&tx.r)),
+                ("s", quantity(&tx.s)),
+            ],
+        };
+
+        Ok(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{word, Field};
+    use crate::{
+        AccessList, TransactionAccessList, TransactionBlob, TransactionDynamicFee,
+        TransactionEnvelope, U256,
+    };
+    use rlp_rs::RlpError;
+
+    #[test]
+    fn words() {
+        let tests = [
+            (U256::default(), Ok([0; 32])),
+            (vec![0x01].try_into().unwrap(), {
+                let mut word = [0; 32];
+                word[31] = 0x01;
+                Ok(word)
+            }),
+            (U256::from([0xff; 32]), Ok([0xff; 32])),
+        ];
+
+        for (i, (value, expected)) in tests.into_iter().enumerate() {
+            println!("{i}...");
+
+            assert_eq!(word(&value), expected);
+
+            println!("ok");
+        }
+    }
+
+    #[test]
+    fn oversized_words() {
+        let key: U256 = [2; 32].into();
+        let tx = TransactionEnvelope::DynamicFee(TransactionDynamicFee {
+            access_list: vec![AccessList {
+                address: [1; 20].into(),
+                storage_keys: vec![key],
+            }],
+            ..Default::default()
+        });
+        let fields = tx.fields().unwrap();
+        assert_eq!(fields[0].0, "chain_id");
+        assert_eq!(
+            fields[8],
+            (
+                "access_list",
+                Field::AccessList(vec![(&[1; 20][..], vec![[2; 32]])])
+            )
+        );
+
+        // decoding doesn't bound the length of integers
+        let key: U256 = rlp_rs::from_bytes(&[[0xa1].as_slice(), &[2; 33]].concat()).unwrap();
+        let tx = TransactionEnvelope::Blob(TransactionBlob {
+            blob_hashes: vec![key.clone()],
+            ..Default::default()
+        });
+        assert_eq!(tx.fields(), Err(RlpError::InvalidLength));
+
+        let tx = TransactionEnvelope::AccessList(TransactionAccessList {
+            access_list: vec![AccessList {
+                address: Default::default(),
+                storage_keys: vec![key],
+            }],
+            ..Default::default()
+        });
+        assert_eq!(tx.fields(), Err(RlpError::InvalidLength));
+    }
+}
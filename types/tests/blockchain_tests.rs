@@ -0,0 +1,291 @@
+//! Runs fixtures in the `BlockchainTests` format of the Ethereum tests and execution-spec-tests.
+//! Upstream fixtures are vendored in `tests/fixtures/BlockchainTests`, and the ones generated
+//! locally in that format in `tests/fixtures/GeneratedBlockchainTests`, see their README.
+//! Set `BLOCKCHAIN_TESTS` to a directory of upstream fixtures to run those instead.
+//!
+//! Every `rlp` is decoded with `Block::from_bytes` and checked against its `blockHeader`, the
+//! block hash against `hash`, and the hash of the last valid block against `lastblockhash`.
+//! Blocks with an `expectException` are flagged; the ones rejected for their encoding must fail
+//! to decode, the others are invalid only once executed. A test whose headers carry a field newer
+//! than Cancun fails the run, after every other test ran.
+
+use rlp_types::{Block, Header};
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use std::path::{Path, PathBuf};
+
+const HEADER_FIELDS: [&str; 21] = [
+    "parentHash",
+    "uncleHash",
+    "coinbase",
+    "stateRoot",
+    "transactionsTrie",
+    "receiptTrie",
+    "bloom",
+    "difficulty",
+    "number",
+    "gasLimit",
+    "gasUsed",
+    "timestamp",
+    "extraData",
+    "mixHash",
+    "nonce",
+    "baseFeePerGas",
+    "withdrawalsRoot",
+    "blobGasUsed",
+    "excessBlobGas",
+    "parentBeaconBlockRoot",
+    "hash",
+];
+
+const QUANTITIES: [&str; 8] = [
+    "difficulty",
+    "number",
+    "gasLimit",
+    "gasUsed",
+    "timestamp",
+    "baseFeePerGas",
+    "blobGasUsed",
+    "excessBlobGas",
+];
+
+fn fixtures_dirs() -> Vec<PathBuf> {
+    if let Some(dir) = std::env::var_os("BLOCKCHAIN_TESTS") {
+        return vec![PathBuf::from(dir)];
+    }
+
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    ["BlockchainTests", "GeneratedBlockchainTests"]
+        .into_iter()
+        .map(|dir| fixtures.join(dir))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+fn fixture_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files.append(&mut fixture_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
+
+fn bytes(value: &Value) -> Vec<u8> {
+    let value = value.as_str().unwrap();
+    hex::decode(value.strip_prefix("0x").unwrap_or(value)).unwrap()
+}
+
+fn trim(bytes: &[u8]) -> Vec<u8> {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    bytes[start..].to_vec()
+}
+
+fn header_fields(header: &Header) -> Vec<(&'static str, Vec<u8>)> {
+    let mut fields = vec![
+        ("parentHash", header.parent_hash().as_ref().to_vec()),
+        ("uncleHash", header.uncle_hash().as_ref().to_vec()),
+        ("coinbase", header.coinbase().as_ref().to_vec()),
+        ("stateRoot", header.state_root().as_ref().to_vec()),
+        ("transactionsTrie", header.tx_root().as_ref().to_vec()),
+        ("receiptTrie", header.receipt_hash().as_ref().to_vec()),
+        ("bloom", header.bloom().as_ref().to_vec()),
+        ("difficulty", header.difficulty().as_ref().to_vec()),
+        ("number", header.number().as_ref().to_vec()),
+        ("gasLimit", header.gas_limit().to_be_bytes().to_vec()),
+        ("gasUsed", header.gas_used().to_be_bytes().to_vec()),
+        ("timestamp", header.time().to_be_bytes().to_vec()),
+        ("extraData", header.extra().clone()),
+        ("mixHash", header.mix_digest().as_ref().to_vec()),
+        ("nonce", header.nonce().as_ref().to_vec()),
+    ];
+
+    match header {
+        Header::London { base_fee, .. } => {
+            fields.push(("baseFeePerGas", base_fee.as_ref().to_vec()));
+        }
+        Header::Shanghai {
+            base_fee,
+            withdrawal_root,
+            ..
+        } => {
+            fields.push(("baseFeePerGas", base_fee.as_ref().to_vec()));
+            fields.push(("withdrawalsRoot", withdrawal_root.as_ref().to_vec()));
+        }
+        Header::Cancun {
+            base_fee,
+            withdrawal_root,
+            blob_gas_used,
+            excess_blob_gas,
+            parent_beacon_block_root,
+            ..
+        } => {
+            fields.push(("baseFeePerGas", base_fee.as_ref().to_vec()));
+            fields.push(("withdrawalsRoot", withdrawal_root.as_ref().to_vec()));
+            fields.push(("blobGasUsed", blob_gas_used.to_be_bytes().to_vec()));
+            fields.push(("excessBlobGas", excess_blob_gas.to_be_bytes().to_vec()));
+            fields.push((
+                "parentBeaconBlockRoot",
+                parent_beacon_block_root.as_ref().to_vec(),
+            ));
+        }
+        Header::Legacy { .. } | Header::Unknown { .. } => {}
+    }
+
+    fields
+}
+
+fn check_header(name: &str, header: &Header, hash: [u8; 32], expected: &Value) {
+    let fields = header_fields(header);
+    for (field, actual) in &fields {
+        let value = expected
+            .get(field)
+            .unwrap_or_else(|| panic!("{name}: decoded a {field} missing from the fixture"));
+        if QUANTITIES.contains(field) {
+            assert_eq!(trim(actual), trim(&bytes(value)), "{name}: {field}");
+        } else {
+            assert_eq!(*actual, bytes(value), "{name}: {field}");
+        }
+    }
+
+    let expected = expected.as_object().unwrap();
+    for field in expected.keys() {
+        assert!(
+            field == "hash" || fields.iter().any(|(f, _)| f == field),
+            "{name}: {field} was not decoded"
+        );
+    }
+    assert_eq!(hash.to_vec(), bytes(&expected["hash"]), "{name}: hash");
+}
+
+fn check_block(name: &str, block: &Block, expected: &Value) {
+    let hash = block.hash().unwrap();
+    if let Some(header) = expected.get("blockHeader") {
+        check_header(name, &block.header, hash, header);
+    }
+
+    if let Some(transactions) = expected.get("transactions").and_then(Value::as_array) {
+        assert_eq!(
+            block.transactions.len(),
+            transactions.len(),
+            "{name}: transactions"
+        );
+        for (i, (tx, expected)) in block.transactions.iter().zip(transactions).enumerate() {
+            if let Some(tx_type) = expected.get("type") {
+                let tx_type = bytes(tx_type).last().copied().unwrap_or_default();
+                assert_eq!(tx.tx_type(), tx_type, "{name}: tx {i} type");
+            }
+            if let Some(hash) = expected.get("hash") {
+                assert_eq!(tx.hash().unwrap().to_vec(), bytes(hash), "{name}: tx {i}");
+            }
+        }
+    }
+
+    if let Some(uncles) = expected.get("uncleHeaders").and_then(Value::as_array) {
+        assert_eq!(block.uncles.len(), uncles.len(), "{name}: uncles");
+        for (uncle, expected) in block.uncles.iter().zip(uncles) {
+            let hash = Keccak256::digest(rlp_rs::to_bytes(uncle).unwrap()).into();
+            check_header(name, uncle, hash, expected);
+        }
+    }
+}
+
+fn expected_exception(block: &Value) -> Option<&str> {
+    block.as_object().unwrap().iter().find_map(|(key, value)| {
+        key.starts_with("expectException")
+            .then(|| value.as_str().unwrap_or_default())
+    })
+}
+
+fn unsupported_field(test: &Value) -> Option<&str> {
+    let mut headers = vec![&test["genesisBlockHeader"]];
+    for block in test["blocks"].as_array().unwrap() {
+        headers.push(&block["blockHeader"]);
+        headers.extend(block["uncleHeaders"].as_array().into_iter().flatten());
+    }
+    headers
+        .into_iter()
+        .filter_map(Value::as_object)
+        .flat_map(|header| header.keys())
+        .find(|field| !HEADER_FIELDS.contains(&field.as_str()))
+        .map(String::as_str)
+}
+
+#[test]
+fn blockchain_tests() {
+    let mut blocks = 0;
+    let mut flagged = 0;
+    let mut unsupported = Vec::new();
+
+    for file in fixtures_dirs().iter().flat_map(|dir| fixture_files(dir)) {
+        let content = std::fs::read_to_string(&file).unwrap();
+        let tests: serde_json::Map<String, Value> = serde_json::from_str(&content).unwrap();
+        for (name, test) in tests {
+            println!("{name}...");
+
+            if let Some(field) = unsupported_field(&test) {
+                println!("{field} is not supported");
+                unsupported.push(format!("{name}: {field}"));
+                continue;
+            }
+
+            let mut last_valid = None;
+            if let Some(rlp) = test.get("genesisRLP") {
+                let block = Block::from_bytes(&bytes(rlp)).unwrap();
+                let expected = serde_json::json!({ "blockHeader": test["genesisBlockHeader"] });
+                check_block(&format!("{name} genesis"), &block, &expected);
+                last_valid = Some(block.hash().unwrap().to_vec());
+            }
+
+            for (i, expected) in test["blocks"].as_array().unwrap().iter().enumerate() {
+                let name = format!("{name} block {i}");
+                let Some(rlp) = expected.get("rlp") else {
+                    continue;
+                };
+                let decoded = hex::decode(rlp.as_str().unwrap().trim_start_matches("0x"))
+                    .map_err(|err| err.to_string())
+                    .and_then(|bytes| Block::from_bytes(&bytes).map_err(|err| err.to_string()));
+                blocks += 1;
+
+                match (expected_exception(expected), decoded) {
+                    (Some(exception), decoded) => {
+                        flagged += 1;
+                        match &decoded {
+                            Ok(_) => println!("{name}: expected {exception}, decoded"),
+                            Err(err) => println!("{name}: expected {exception}, {err}"),
+                        }
+                        assert!(
+                            !exception.contains("RLP") || decoded.is_err(),
+                            "{name}: decoded despite {exception}"
+                        );
+                    }
+                    (None, Ok(block)) => {
+                        check_block(&name, &block, expected);
+                        last_valid = Some(block.hash().unwrap().to_vec());
+                    }
+                    (None, Err(err)) => panic!("{name}: {err}"),
+                }
+            }
+
+            if let Some(last) = test.get("lastblockhash") {
+                assert_eq!(last_valid, Some(bytes(last)), "{name}: lastblockhash");
+            }
+
+            println!("ok");
+        }
+    }
+
+    println!("{blocks} blocks, {flagged} flagged as invalid");
+    assert!(blocks > 0);
+    assert!(
+        unsupported.is_empty(),
+        "{} tests with unsupported header fields:\n{}",
+        unsupported.len(),
+        unsupported.join("\n")
+    );
+}
@@ -0,0 +1,57 @@
+//! WebAssembly bindings over `rlp-rs` and `rlp-types`, for browser and Node tooling.
+//!
+//! Bytes are passed as `Uint8Array`, hashes are returned as `0x` hex strings,
+//! and decoded values as plain objects in the shape printed by `rlp-tx --json`.
+
+use rlp_rs::{pack_rlp, unpack_rlp, Rlp};
+use rlp_types::{json, Block, TransactionEnvelope};
+use serde::Serialize;
+use serde_json::Value;
+use serde_wasm_bindgen::Serializer;
+use wasm_bindgen::prelude::*;
+
+/// plain objects and arrays rather than `Map`s.
+fn to_js(value: &Value) -> Result<JsValue, JsError> {
+    Ok(value.serialize(&Serializer::json_compatible())?)
+}
+
+fn hash(hash: [u8; 32]) -> String {
+    format!("0x{}", hex::encode(hash))
+}
+
+/// Decode a single RLP item into nested arrays of `0x` hex strings.
+#[wasm_bindgen]
+pub fn decode_rlp(bytes: &[u8]) -> Result<JsValue, JsError> {
+    to_js(&unpack_rlp(bytes)?.to_json()?)
+}
+
+/// Encode nested arrays of `0x` hex strings, `#` decimal strings, text and non-negative integers.
+#[wasm_bindgen]
+pub fn encode_rlp(value: JsValue) -> Result<Vec<u8>, JsError> {
+    let value: Value = serde_wasm_bindgen::from_value(value)?;
+    Ok(pack_rlp(Rlp::from_json(&value)?)?)
+}
+
+/// Decode a raw transaction into its type, name, hash and fields.
+#[wasm_bindgen]
+pub fn decode_transaction(bytes: &[u8]) -> Result<JsValue, JsError> {
+    to_js(&json::transaction(&TransactionEnvelope::from_raw_bytes(
+        bytes,
+    )?)?)
+}
+
+#[wasm_bindgen]
+pub fn transaction_hash(bytes: &[u8]) -> Result<String, JsError> {
+    Ok(hash(TransactionEnvelope::from_raw_bytes(bytes)?.hash()?))
+}
+
+/// Decode a block into its hash, header, transactions and uncles.
+#[wasm_bindgen]
+pub fn decode_block(bytes: &[u8]) -> Result<JsValue, JsError> {
+    to_js(&json::block(&Block::from_bytes(bytes)?)?)
+}
+
+#[wasm_bindgen]
+pub fn block_hash(bytes: &[u8]) -> Result<String, JsError> {
+    Ok(hash(Block::from_bytes(bytes)?.hash()?))
+}
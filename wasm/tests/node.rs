@@ -0,0 +1,97 @@
+//! The bindings as seen from JavaScript, run under Node with
+//! `cargo test -p rlp-wasm --target wasm32-unknown-unknown`.
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, JSON};
+use rlp_wasm::{
+    block_hash, decode_block, decode_rlp, decode_transaction, encode_rlp, transaction_hash,
+};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const DYNAMIC_FEE: &str = "02f8a0018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8";
+// the dynamic fee transaction with a 33 bytes storage key
+const OVERSIZED_KEY: &str = "02f8a2018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f83af838940000000000000000000000000000000000000001e2a101000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8";
+const LONDON_BLOCK: &str = "f9030bf901fea083cafc574e1f51ba9dc0568fc617a08ea2429fb384059c972f13b19fa1c8dd55a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347948888f1f195afa192cfee860698584c030f4c9db1a0ef1552a40b7165c3cd773806b9e0c165b75356e0314bf0706f279c729f51e017a05fe50b260da6308036625b850b5d6ced6d0a9f814c0688bc91ffb7b7a3a54b67a0bc37d79753ad738a6dac4921e57392f145d8887476de3f783dfa7edae9283e52b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008302000001832fefd8825208845506eb0780a0bd4472abb6659ebe3ee06ee4d7b72a00a9f4d001caca51342001075469aff49888a13a5a8c8f2bb1c4843b9aca00f90106f85f800a82c35094095e7baea6a6c7c4c2dfeb977efac326af552d870a801ba09bea4c4daac7c7c52e093e6a4c35dbbcf8856f1af7b059ba20253e70848d094fa08a8fae537ce25ed8cb5af9adac3f141af69bd515bd2ba031522df09b97dd72b1b8a302f8a0018080843b9aca008301e24194095e7baea6a6c7c4c2dfeb977efac326af552d878080f838f7940000000000000000000000000000000000000001e1a0000000000000000000000000000000000000000000000000000000000000000080a0fe38ca4e44a30002ac54af7cf922a6ac2ba11b7d22f548e8ecb3f51f41cb31b0a06de6a5cbae13c0c856e33acf021b51819636cfc009d39eafb9f606d546e305a8c0";
+
+fn stringify(value: &JsValue) -> String {
+    JSON::stringify(value).unwrap().into()
+}
+
+#[wasm_bindgen_test]
+fn rlp_round_trip() {
+    let bytes = hex::decode("c88363617483646f67").unwrap();
+    let value = decode_rlp(&bytes).unwrap();
+    assert!(Array::is_array(&value));
+    assert_eq!(stringify(&value), r#"["0x636174","0x646f67"]"#);
+    assert_eq!(encode_rlp(value).unwrap(), bytes);
+
+    let value = JSON::parse(r##"[[], "cat", 1024, "#1024", "0x"]"##).unwrap();
+    assert_eq!(
+        hex::encode(encode_rlp(value).unwrap()),
+        "ccc08363617482040082040080"
+    );
+}
+
+#[wasm_bindgen_test]
+fn invalid_rlp() {
+    let tests = [
+        decode_rlp(&[]).map(|_| ()),
+        decode_rlp(&[0x81, 0x00]).map(|_| ()),
+        encode_rlp(JSON::parse("[-1]").unwrap()).map(|_| ()),
+        encode_rlp(JSON::parse(r#"{"a": 1}"#).unwrap()).map(|_| ()),
+        encode_rlp(JsValue::UNDEFINED).map(|_| ()),
+    ];
+
+    for (i, result) in tests.into_iter().enumerate() {
+        println!("{i}...");
+
+        assert!(result.is_err());
+
+        println!("ok");
+    }
+}
+
+#[wasm_bindgen_test]
+fn transaction() {
+    let bytes = hex::decode(DYNAMIC_FEE).unwrap();
+    let tx = decode_transaction(&bytes).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stringify(&tx)).unwrap();
+
+    let hash = transaction_hash(&bytes).unwrap();
+    assert_eq!(json["type"], 2);
+    assert_eq!(json["name"], "dynamic fee");
+    assert_eq!(json["hash"], hash);
+    assert_eq!(json["fields"]["max_fee_per_gas"], "0x3b9aca00");
+    assert_eq!(
+        json["fields"]["access_list"][0]["address"],
+        "0x0000000000000000000000000000000000000001"
+    );
+
+    assert!(decode_transaction(&bytes[..bytes.len() - 1]).is_err());
+    assert!(transaction_hash(&[0x05, 0xc0]).is_err());
+
+    let oversized = hex::decode(OVERSIZED_KEY).unwrap();
+    assert!(transaction_hash(&oversized).is_ok());
+    assert!(decode_transaction(&oversized).is_err());
+}
+
+#[wasm_bindgen_test]
+fn block() {
+    let bytes = hex::decode(LONDON_BLOCK).unwrap();
+    let block = decode_block(&bytes).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stringify(&block)).unwrap();
+
+    assert_eq!(json["hash"], block_hash(&bytes).unwrap());
+    assert_eq!(json["header"]["fork"], "london");
+    assert_eq!(json["header"]["fields"]["base_fee"], "0x3b9aca00");
+    assert_eq!(json["transactions"][0]["name"], "legacy");
+    assert_eq!(json["transactions"][1]["hash"], {
+        let tx = hex::decode(DYNAMIC_FEE).unwrap();
+        transaction_hash(&tx).unwrap()
+    });
+    assert_eq!(json["uncles"], serde_json::json!([]));
+
+    assert!(decode_block(&bytes[1..]).is_err());
+    assert!(block_hash(&[0xc0]).is_err());
+}